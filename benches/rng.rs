@@ -0,0 +1,57 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::prelude::*;
+
+use betting_problem::*;
+
+const ROLLS: u64 = 100_000;
+
+fn roll(c: &mut Criterion) {
+    let mut group = c.benchmark_group("roll");
+    group.bench_function("thread_rng per roll", |b| {
+        b.iter(|| {
+            for _ in 0..ROLLS {
+                black_box(Dice::roll());
+            }
+        })
+    });
+    group.bench_function("cached rng", |b| {
+        b.iter(|| {
+            let mut rng = thread_rng();
+            for _ in 0..ROLLS {
+                black_box(Dice::roll_with_rng(&mut rng));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn avg_outcome(c: &mut Criterion) {
+    let mut group = c.benchmark_group("avg_outcome");
+    group.sample_size(20);
+    group.bench_function("NoPower", |b| {
+        b.iter(|| {
+            NoPower().avg_outcome(
+                black_box(ROLLS),
+                &GameRules::default(),
+                1.0,
+                &PowerCosts::default(),
+                &mut thread_rng(),
+            )
+        })
+    });
+    group.bench_function("RerollIfLosingOrFlip", |b| {
+        b.iter(|| {
+            RerollIfLosingOrFlip().avg_outcome(
+                black_box(ROLLS),
+                &GameRules::default(),
+                1.0,
+                &PowerCosts::default(),
+                &mut thread_rng(),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, roll, avg_outcome);
+criterion_main!(benches);
@@ -0,0 +1,153 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use serde::Serialize;
+
+use crate::{GameRules, SimulationError};
+
+// A bet the dice can actually meet: between the smallest and largest possible
+// sums, which is as wide as the sums themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Bet(u16);
+
+impl Bet {
+    pub fn new(value: u16, rules: &GameRules) -> Result<Self, SimulationError> {
+        let range = rules.bet_range();
+        if range.contains(&value) {
+            Ok(Bet(value))
+        } else {
+            Err(SimulationError::InvalidBet {
+                bet: value,
+                min: *range.start(),
+                max: *range.end(),
+            })
+        }
+    }
+
+    // For bets already known to be in range.
+    pub(crate) fn unchecked(value: u16) -> Self {
+        Bet(value)
+    }
+
+    pub fn value(self) -> u16 {
+        self.0
+    }
+}
+
+impl GameRules {
+    // From the smallest to the largest sum of two dice, or to the cap on bets
+    // when dice explode.
+    pub fn bet_range(&self) -> RangeInclusive<u16> {
+        let max = match self.exploding {
+            Some(max) => max,
+            None => u16::from(self.faces.max()) * 2,
+        };
+        u16::from(self.faces.min()) * 2..=max
+    }
+
+    // Exploding dice can't be enumerated, since their sums have no limit.
+    pub fn check_enumerable(&self) -> Result<(), SimulationError> {
+        match self.exploding {
+            Some(_) => Err(SimulationError::NotEnumerable(
+                "exploding dice have no exact solution, so sample them instead".to_owned(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn bets(&self) -> impl Iterator<Item = Bet> {
+        self.bet_range().map(Bet)
+    }
+
+    pub fn min_bet(&self) -> Bet {
+        Bet(*self.bet_range().start())
+    }
+}
+
+impl From<Bet> for u16 {
+    fn from(bet: Bet) -> u16 {
+        bet.0
+    }
+}
+
+// Bets compare directly against sums.
+impl PartialEq<u16> for Bet {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialOrd<u16> for Bet {
+    fn partial_cmp(&self, other: &u16) -> Option<Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+impl PartialEq<Bet> for u16 {
+    fn eq(&self, other: &Bet) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialOrd<Bet> for u16 {
+    fn partial_cmp(&self, other: &Bet) -> Option<Ordering> {
+        self.partial_cmp(&other.0)
+    }
+}
+
+impl fmt::Display for Bet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bets_the_dice_cant_meet_are_rejected() {
+        let rules = GameRules::default();
+        for bet in [0, 1, 13, 40, 255] {
+            assert!(matches!(
+                Bet::new(bet, &rules),
+                Err(SimulationError::InvalidBet {
+                    min: 2,
+                    max: 12,
+                    ..
+                })
+            ));
+        }
+        assert_eq!(Bet::new(7, &rules).unwrap().value(), 7);
+    }
+
+    #[test]
+    fn the_range_follows_the_dice() {
+        let fibonacci = GameRules {
+            faces: "1,1,2,3,5,8".parse().unwrap(),
+            ..GameRules::default()
+        };
+        assert_eq!(fibonacci.bet_range(), 2..=16);
+        assert!(Bet::new(16, &fibonacci).is_ok());
+        assert!(Bet::new(17, &fibonacci).is_err());
+        let high = GameRules {
+            faces: "3,4,5".parse().unwrap(),
+            ..GameRules::default()
+        };
+        assert!(Bet::new(5, &high).is_err());
+        assert!(Bet::new(6, &high).is_ok());
+        let wide = GameRules {
+            faces: "200,250".parse().unwrap(),
+            ..GameRules::default()
+        };
+        assert_eq!(wide.bet_range(), 400..=500);
+        assert!(Bet::new(450, &wide).is_ok());
+        let exploding = GameRules {
+            exploding: Some(30),
+            ..GameRules::default()
+        };
+        assert!(Bet::new(30, &exploding).is_ok());
+        assert!(Bet::new(31, &exploding).is_err());
+    }
+}
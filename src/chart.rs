@@ -0,0 +1,129 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Gold, Outcome, SimulationError};
+
+// How outcomes are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Chart,
+    Latex,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Chart => "chart",
+            OutputFormat::Latex => "latex",
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = SimulationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(OutputFormat::Table),
+            "chart" => Ok(OutputFormat::Chart),
+            "latex" => Ok(OutputFormat::Latex),
+            _ => Err(SimulationError::UnknownOutputFormat(s.to_owned())),
+        }
+    }
+}
+
+// A difference between two outcomes, from `Outcome::diff`, with gains in green
+// and losses in red when `color` is set.
+#[derive(Debug)]
+pub struct Diff<'a> {
+    pub outcome: &'a Outcome,
+    pub color: bool,
+    // From `compare::compare_significance`, or empty to leave the column out.
+    pub significance: &'a [Option<Ordering>],
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.significance.is_empty() {
+            writeln!(f, "Bet |  Diff")?;
+            writeln!(f, "--- | -----")?;
+        } else {
+            writeln!(f, "Bet |  Diff | Significant")?;
+            writeln!(f, "--- | ----- | -----------")?;
+        }
+        for (i, (bet, diff)) in self.outcome.iter().enumerate() {
+            let (start, end) = match self.color {
+                true if diff > 0.0 => ("\x1b[32m", "\x1b[0m"),
+                true if diff < 0.0 => ("\x1b[31m", "\x1b[0m"),
+                _ => ("", ""),
+            };
+            write!(f, "{:>3} | {}{:>+5.2}{}", bet, start, diff, end)?;
+            match self.significance.get(i) {
+                Some(Some(Ordering::Greater)) => write!(f, " | better")?,
+                Some(Some(Ordering::Less)) => write!(f, " | worse")?,
+                Some(_) => write!(f, " |")?,
+                None => {}
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// A bar of `#`s per bet, scaled so the best bet's bar is `width` long. Bets
+// that lose gold get no bar.
+#[derive(Debug)]
+pub struct Chart<'a> {
+    pub outcome: &'a Outcome,
+    pub width: usize,
+}
+
+// A cumulative distribution as a staircase: each gold value's bar is as long as
+// the chance of getting at most that much, so the bars only ever grow.
+pub struct Cdf<'a> {
+    pub points: &'a [(Gold, f64)],
+    pub width: usize,
+}
+
+impl fmt::Display for Cdf<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Gold | P(<= gold)")?;
+        writeln!(f, "---- | ----------")?;
+        for &(gold, p) in self.points {
+            writeln!(
+                f,
+                " {:>3} | {:<width$} {:.4}",
+                gold,
+                "#".repeat((p * self.width as f64).round() as usize),
+                p,
+                width = self.width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Chart<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max = self.outcome.ev.iter().copied().fold(0.0, f64::max);
+        for (bet, ev) in self.outcome.iter() {
+            let length = if max > 0.0 && ev > 0.0 {
+                (ev / max * self.width as f64).round() as usize
+            } else {
+                0
+            };
+            writeln!(
+                f,
+                " {:>2} | {:<width$} {:.2}",
+                bet,
+                "#".repeat(length),
+                ev,
+                width = self.width
+            )?;
+        }
+        Ok(())
+    }
+}
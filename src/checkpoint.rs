@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::Path;
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::stats::RunningStats;
+use crate::{check_probability, Bet, GameRules, Outcome, PowerCosts, SimulationError, Strategy};
+
+// Bumped whenever the file layout or the chunk seeding changes, since either
+// would make a resumed run differ from an uninterrupted one.
+const VERSION: u32 = 3;
+
+// Trials played at each bet between saves. Every chunk of every bet draws from
+// its own seed, so a resumed run plays exactly the rolls an uninterrupted run
+// would.
+pub const CHUNK: u64 = 1_000_000;
+
+// A long simulation's progress, saved as JSON so it can be stopped and picked
+// up again. It carries the game it was started with, so a resumed run can't
+// quietly switch rules partway through.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Checkpoint {
+    version: u32,
+    pub seed: u64,
+    consolation: u8,
+    payout: String,
+    faces: String,
+    roll: String,
+    exploding: Option<u16>,
+    pub power_availability: f64,
+    reroll_cost: i64,
+    flip_cost: i64,
+    strategies: Vec<Progress>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Progress {
+    name: String,
+    trials: u64,
+    // One per bet, in increasing order of bet.
+    stats: Vec<(u16, RunningStats)>,
+}
+
+impl Checkpoint {
+    pub fn new(
+        seed: u64,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+    ) -> Result<Self, SimulationError> {
+        Ok(Self {
+            version: VERSION,
+            seed,
+            consolation: rules.consolation,
+            payout: rules.payout.to_string(),
+            faces: rules.faces.to_string(),
+            roll: rules.roll.to_string(),
+            exploding: rules.exploding,
+            power_availability: check_probability(power_availability)?,
+            reroll_cost: costs.reroll,
+            flip_cost: costs.flip,
+            strategies: Vec::new(),
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SimulationError> {
+        // The version is checked on its own first, since another version's
+        // layout won't parse as this one's.
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+
+        let json = fs::read_to_string(path)?;
+        let invalid = |err: serde_json::Error| SimulationError::InvalidCheckpoint(err.to_string());
+        let Versioned { version } = serde_json::from_str(&json).map_err(invalid)?;
+        if version != VERSION {
+            return Err(SimulationError::InvalidCheckpoint(format!(
+                "version {}, expected {}",
+                version, VERSION
+            )));
+        }
+        let checkpoint: Checkpoint = serde_json::from_str(&json).map_err(invalid)?;
+        let rules = checkpoint.rules()?;
+        check_probability(checkpoint.power_availability)?;
+        for progress in &checkpoint.strategies {
+            let bets: Vec<u16> = progress.stats.iter().map(|&(bet, _)| bet).collect();
+            if !bets.iter().copied().eq(rules.bets().map(Bet::value)) {
+                return Err(SimulationError::InvalidCheckpoint(format!(
+                    "{:?} covers bets {:?}, not the game's",
+                    progress.name, bets
+                )));
+            }
+        }
+        Ok(checkpoint)
+    }
+
+    // Writes to a temporary file first, so an interrupted save leaves the last
+    // checkpoint intact.
+    pub fn save(&self, path: &Path) -> Result<(), SimulationError> {
+        let json = serde_json::to_string(self)
+            .map_err(|err| SimulationError::InvalidCheckpoint(err.to_string()))?;
+        let partial = path.with_extension("partial");
+        fs::write(&partial, json)?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    pub fn rules(&self) -> Result<GameRules, SimulationError> {
+        Ok(GameRules {
+            consolation: self.consolation,
+            payout: self.payout.parse()?,
+            faces: self.faces.parse()?,
+            roll: self.roll.parse()?,
+            exploding: self.exploding,
+        })
+    }
+
+    pub fn costs(&self) -> Result<PowerCosts, SimulationError> {
+        PowerCosts {
+            reroll: self.reroll_cost,
+            flip: self.flip_cost,
+        }
+        .check()
+    }
+
+    // Plays `strategy` up to `trials` per bet, carrying on from wherever it
+    // got to before, and saves to `path` after every chunk.
+    pub fn run<S: Strategy + ?Sized>(
+        &mut self,
+        strategy: &S,
+        trials: u64,
+        path: &Path,
+    ) -> Result<Outcome, SimulationError> {
+        if trials == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        let (rules, costs) = (self.rules()?, self.costs()?);
+        let index = match self
+            .strategies
+            .iter()
+            .position(|progress| progress.name == strategy.name())
+        {
+            Some(index) => index,
+            None => {
+                self.strategies.push(Progress {
+                    name: strategy.name().to_owned(),
+                    trials: 0,
+                    stats: rules
+                        .bets()
+                        .map(|bet| (bet.value(), RunningStats::default()))
+                        .collect(),
+                });
+                self.strategies.len() - 1
+            }
+        };
+        while self.strategies[index].trials < trials {
+            let done = self.strategies[index].trials;
+            let (chunk, offset) = (done / CHUNK, done % CHUNK);
+            let end = ((chunk + 1) * CHUNK).min(trials);
+            for (bet, stats) in &mut self.strategies[index].stats {
+                let bet = Bet::new(*bet, &rules)?;
+                let mut rng =
+                    SmallRng::seed_from_u64(self.seed ^ (chunk << 8) ^ u64::from(bet.value()));
+                // A chunk cut short by an earlier `trials` is replayed up to
+                // where it stopped.
+                for _ in 0..offset {
+                    strategy.outcome(bet, &rules, self.power_availability, &costs, &mut rng);
+                }
+                for _ in done..end {
+                    stats.push(
+                        strategy
+                            .outcome(bet, &rules, self.power_availability, &costs, &mut rng)
+                            .into(),
+                    );
+                }
+            }
+            self.strategies[index].trials = end;
+            self.save(path)?;
+        }
+        self.strategies[index]
+            .stats
+            .iter()
+            .map(|&(bet, stats)| Ok((Bet::new(bet, &rules)?, stats)))
+            .collect()
+    }
+}
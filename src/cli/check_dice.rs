@@ -0,0 +1,48 @@
+use std::io::Write;
+
+use betting_problem::SimulationError;
+
+use super::SharedArgs;
+
+pub fn run(shared: &SharedArgs, out: &mut dyn Write) -> Result<(), SimulationError> {
+    let rules = shared.rules();
+    rules.check_enumerable()?;
+    let range = rules.bet_range();
+    writeln!(
+        out,
+        "faces = {}, roll = {}, bets = {}..={}",
+        rules.faces,
+        rules.roll,
+        range.start(),
+        range.end()
+    )?;
+    writeln!(out, "Sum | P")?;
+    writeln!(out, "--- | -")?;
+    let rolls = rules.roll.outcomes(rules.faces);
+    for sum in range {
+        let p: f64 = rolls
+            .iter()
+            .filter(|(dice, _)| dice.sum() == sum)
+            .map(|(_, p)| p)
+            .sum();
+        if p > 0.0 {
+            writeln!(out, "{:>3} | {:.4}", sum, p)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn takes_the_dice_from_the_shared_flags() {
+        let args =
+            Args::try_parse_from(["betting-problem", "check-dice", "--roll", "advantage"]).unwrap();
+        assert!(matches!(args.command, Some(Command::CheckDice)));
+        assert_eq!(args.shared.roll, "advantage".parse().unwrap());
+    }
+}
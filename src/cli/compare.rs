@@ -0,0 +1,158 @@
+use std::io::{self, IsTerminal, Write};
+
+use clap::ArgMatches;
+use rand::random;
+
+use betting_problem::chart::Diff;
+use betting_problem::compare;
+use betting_problem::config::Config;
+use betting_problem::simulator::SimulationBuilder;
+use betting_problem::*;
+
+use super::{unset, SharedArgs, StrategyArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct CompareArgs {
+    pub a: String,
+
+    pub b: String,
+
+    /// Without a bet, show the difference in expected gold at every bet.
+    #[arg(value_name = "BET")]
+    pub at: Option<u16>,
+
+    /// Rounds played per bet.
+    #[arg(long, default_value_t = 1_000_000)]
+    pub trials: u64,
+
+    /// Without a bet, enumerate every roll instead of sampling.
+    #[arg(long)]
+    pub exact: bool,
+
+    #[command(flatten)]
+    pub strategies: StrategyArgs,
+}
+
+impl CompareArgs {
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        if unset(matches, "trials") {
+            self.trials = config.simulation.trials;
+        }
+        self.strategies.apply_config(config, matches);
+    }
+}
+
+pub fn run(
+    args: &CompareArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let color = shared.output.is_none() && io::stdout().is_terminal();
+    let rules = shared.rules();
+    let strategies = args.strategies.strategies(shared, config)?;
+    let (a, b) = (
+        find_strategy(&strategies, &args.a)?,
+        find_strategy(&strategies, &args.b)?,
+    );
+    let bet = match args.at {
+        Some(bet) => Bet::new(bet, &rules)?,
+        None => {
+            let mut simulation = SimulationBuilder::default()
+                .mode(if args.exact {
+                    Mode::Exact
+                } else {
+                    Mode::MonteCarlo(args.trials)
+                })
+                .rules(rules)
+                .power_availability(shared.power_availability)
+                .costs(shared.costs());
+            if let Some(seed) = shared.seed {
+                simulation = simulation.seed(seed);
+            }
+            let (a_outcome, b_outcome) = (
+                config.restrict(&simulation.run(a)?, &rules)?,
+                config.restrict(&simulation.run(b)?, &rules)?,
+            );
+            let significance = if args.exact {
+                Vec::new()
+            } else {
+                compare::compare_significance(&a_outcome, &b_outcome, args.trials, 0.05)?
+            };
+            writeln!(
+                out,
+                "{} - {}:\n{}",
+                a.name(),
+                b.name(),
+                Diff {
+                    outcome: &a_outcome.diff(&b_outcome)?,
+                    color,
+                    significance: &significance,
+                }
+            )?;
+            return Ok(());
+        }
+    };
+    let comparison = compare::compare_strategies(
+        a,
+        b,
+        bet,
+        args.trials,
+        shared.seed.unwrap_or_else(random),
+        &rules,
+        shared.power_availability,
+        &shared.costs(),
+    )?;
+    writeln!(
+        out,
+        "{} - {} at bet {}: {:+.4} ± {:.4}{}",
+        a.name(),
+        b.name(),
+        bet,
+        comparison.mean_difference,
+        comparison.std_error,
+        if comparison.is_significant() {
+            " (significant)"
+        } else {
+            ""
+        }
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn parses_both_strategies_and_a_bet() {
+        let args = Args::try_parse_from([
+            "betting-problem",
+            "compare",
+            "NoChange",
+            "RerollIfLosing",
+            "7",
+            "--trials",
+            "100",
+        ])
+        .unwrap();
+        match args.command {
+            Some(Command::Compare(compare)) => {
+                assert_eq!(
+                    (compare.a.as_str(), compare.b.as_str()),
+                    ("NoChange", "RerollIfLosing")
+                );
+                assert_eq!(compare.at, Some(7));
+                assert_eq!(compare.trials, 100);
+                assert!(!compare.exact);
+            }
+            command => panic!("parsed {:?}", command),
+        }
+        assert!(Args::try_parse_from(["betting-problem", "compare", "a"]).is_err());
+        assert!(
+            Args::try_parse_from(["betting-problem", "compare", "a", "b", "--session"]).is_err()
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::io::Write;
+
+use betting_problem::markov::DecisionTable;
+use betting_problem::SimulationError;
+
+use super::SharedArgs;
+
+#[derive(Debug, clap::Args)]
+pub struct DecisionTableArgs {
+    /// Print the tables as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(
+    args: &DecisionTableArgs,
+    shared: &SharedArgs,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let table = DecisionTable::new(&shared.rules(), &shared.costs())?;
+    if args.json {
+        writeln!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&table).expect("a decision table is valid JSON")
+        )?;
+    } else {
+        write!(out, "{}", table)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn parses_json() {
+        let args = Args::try_parse_from(["betting-problem", "decision-table", "--json"]).unwrap();
+        match args.command {
+            Some(Command::DecisionTable(table)) => assert!(table.json),
+            command => panic!("parsed {:?}", command),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::io::Write;
+
+use clap::ArgMatches;
+
+use betting_problem::config::Config;
+use betting_problem::SimulationError;
+
+use super::simulate::{self, ReportArgs, SimulateArgs};
+use super::{SharedArgs, StrategyArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct ExactArgs {
+    #[command(flatten)]
+    pub report: ReportArgs,
+
+    #[command(flatten)]
+    pub strategies: StrategyArgs,
+}
+
+impl ExactArgs {
+    pub fn apply_config(
+        &mut self,
+        config: &Config,
+        matches: &ArgMatches,
+    ) -> Result<(), SimulationError> {
+        self.report.apply_config(config, matches)?;
+        self.strategies.apply_config(config, matches);
+        Ok(())
+    }
+}
+
+pub fn run(
+    args: &ExactArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let simulate = SimulateArgs {
+        report: args.report.clone(),
+        strategies: args.strategies.clone(),
+        exact: true,
+        ..SimulateArgs::default()
+    };
+    simulate::run(&simulate, shared, config, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn takes_report_flags_but_not_sampling_ones() {
+        let args = Args::try_parse_from([
+            "betting-problem",
+            "exact",
+            "--bet",
+            "7",
+            "--distribution",
+            "--format",
+            "chart",
+        ])
+        .unwrap();
+        match args.command {
+            Some(Command::Exact(exact)) => {
+                assert_eq!(exact.report.bet, Some(7));
+                assert!(exact.report.distribution);
+                assert_eq!(exact.report.chart_width, 50);
+            }
+            command => panic!("parsed {:?}", command),
+        }
+        assert!(Args::try_parse_from(["betting-problem", "exact", "--trials", "5"]).is_err());
+        assert!(Args::try_parse_from(["betting-problem", "exact", "--antithetic"]).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use std::io::Write;
+
+use betting_problem::SimulationError;
+
+use super::SharedArgs;
+
+pub fn run(shared: &SharedArgs, out: &mut dyn Write) -> Result<(), SimulationError> {
+    for strategy in betting_problem::strategies(&shared.rules(), &shared.costs()) {
+        writeln!(out, "{}: {}", strategy.name(), strategy.description())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn takes_no_flags_of_its_own() {
+        let args = Args::try_parse_from(["betting-problem", "list-strategies"]).unwrap();
+        assert!(matches!(args.command, Some(Command::ListStrategies)));
+        assert!(
+            Args::try_parse_from(["betting-problem", "list-strategies", "--tree", "t.json"])
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,484 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Parser, Subcommand};
+
+use betting_problem::config::Config;
+use betting_problem::dtree::DTree;
+use betting_problem::empirical::EmpiricalStrategy;
+use betting_problem::evolve::evolve_strategy;
+use betting_problem::search::PolicyTable;
+use betting_problem::simulator::rng_from;
+use betting_problem::*;
+
+mod check_dice;
+mod compare;
+mod decision_table;
+mod exact;
+mod list_strategies;
+mod optimize;
+mod play;
+mod policy_search;
+mod record;
+mod repl;
+mod replay;
+mod simulate;
+mod tournament;
+mod versus;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    // Without a subcommand, the flags of `simulate`. Listed before `command`
+    // since building it needs to see which subcommand, if any, was given.
+    #[command(flatten)]
+    pub simulate: TopLevel,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub shared: SharedArgs,
+}
+
+// The flags of `simulate`, accepted before any subcommand so that a bare run
+// keeps working. Shared flags are global and may come first too, which rules
+// out clap's `args_conflicts_with_subcommands`; instead a `simulate` flag
+// given alongside another subcommand is rejected here rather than ignored.
+#[derive(Debug)]
+pub struct TopLevel(pub simulate::SimulateArgs);
+
+impl clap::FromArgMatches for TopLevel {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        if let Some(name) = matches.subcommand_name() {
+            let command = <simulate::SimulateArgs as clap::Args>::augment_args(clap::Command::new(
+                "simulate",
+            ));
+            for arg in command.get_arguments() {
+                if matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine) {
+                    return Err(clap::Error::raw(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        format!(
+                            "--{} is a flag of `simulate` and can't be used with `{}`\n",
+                            arg.get_long().unwrap_or_else(|| arg.get_id().as_str()),
+                            name
+                        ),
+                    ));
+                }
+            }
+        }
+        simulate::SimulateArgs::from_arg_matches(matches).map(TopLevel)
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        *self = Self::from_arg_matches(matches)?;
+        Ok(())
+    }
+}
+
+impl clap::Args for TopLevel {
+    fn augment_args(command: clap::Command) -> clap::Command {
+        simulate::SimulateArgs::augment_args(command)
+    }
+
+    fn augment_args_for_update(command: clap::Command) -> clap::Command {
+        simulate::SimulateArgs::augment_args_for_update(command)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Estimate every strategy's expected gold at each bet (the default).
+    Simulate(simulate::SimulateArgs),
+    /// Like `simulate --exact`: enumerate every roll instead of sampling.
+    Exact(exact::ExactArgs),
+    /// Play two strategies against the same rolls at one bet and report
+    /// whether the first does significantly better, or show how much more
+    /// the first earns at each bet.
+    Compare(compare::CompareArgs),
+    /// Show the dice in play and the chance of each sum.
+    CheckDice,
+    /// Rank every strategy at its best bet, comparing them on the same rolls.
+    Tournament(tournament::TournamentArgs),
+    /// Search a grid of threshold strategies for the best margin and flip
+    /// threshold at each bet, then print every candidate as CSV.
+    Optimize(optimize::OptimizeArgs),
+    /// Find the best power for every bet and roll by hill climbing, solving
+    /// exactly where the dice allow and sampling --trials rounds otherwise.
+    PolicySearch(policy_search::PolicySearchArgs),
+    /// Play --rounds rounds of a strategy at one bet, printing each and
+    /// saving every roll to a file to replay later.
+    Record(record::RecordArgs),
+    /// Play a strategy at one bet against the rolls saved by `record`, until
+    /// they run out.
+    Replay(replay::ReplayArgs),
+    /// Play two strategies head to head for --trials rounds, each at their
+    /// own bet, and count how often the first ends a round with more gold.
+    Versus(versus::VersusArgs),
+    /// Show what to do with every roll at every bet, in a grid per bet.
+    DecisionTable(decision_table::DecisionTableArgs),
+    /// Print the name and a description of every built-in strategy.
+    ListStrategies,
+    /// Play the game yourself, one round at a time.
+    Play,
+    /// Explore interactively: roll, play single rounds, or look up expected
+    /// gold, one command per line.
+    Repl(repl::ReplArgs),
+}
+
+// The game and its randomness, which every subcommand shares.
+#[derive(Debug, clap::Args)]
+pub struct SharedArgs {
+    /// Seed the random number generator, so sampled results repeat exactly.
+    #[arg(long, global = true)]
+    pub seed: Option<u64>,
+
+    /// Load the experiment from this TOML file. Flags given on the command
+    /// line win over it.
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Write the results to this file instead of stdout.
+    #[arg(long, global = true)]
+    pub output: Option<PathBuf>,
+
+    /// Probability that the power is charged on any given round.
+    #[arg(long, default_value_t = 1.0, global = true)]
+    pub power_availability: f64,
+
+    /// Gold paid for a losing roll.
+    #[arg(long, default_value_t = GameRules::default().consolation, global = true)]
+    pub consolation: u8,
+
+    /// What a winning roll pays: the bet, or the sum of the dice.
+    #[arg(long, default_value_t = PayoutRule::Bet, global = true)]
+    pub payout: PayoutRule,
+
+    /// The comma-separated values on each die's faces, up to 20 of them.
+    #[arg(long, default_value_t = Faces::default(), global = true)]
+    pub faces: Faces,
+
+    /// How the opening roll is made: normal, or advantage or disadvantage to
+    /// roll three dice and keep the two highest or lowest.
+    #[arg(long, default_value_t = RollMode::Normal, global = true)]
+    pub roll: RollMode,
+
+    /// Roll again for each die showing its highest face, adding to the sum.
+    /// Exploding dice can only be sampled.
+    #[arg(long, global = true)]
+    pub exploding: bool,
+
+    /// The highest bet with --exploding [default: four times the highest face]
+    #[arg(long, requires = "exploding", global = true)]
+    pub max_bet: Option<u16>,
+
+    /// Gold charged for using the reroll.
+    #[arg(long, default_value_t = 0, global = true)]
+    pub reroll_cost: i64,
+
+    /// Gold charged for using the flip.
+    #[arg(long, default_value_t = 0, global = true)]
+    pub flip_cost: i64,
+}
+
+impl SharedArgs {
+    pub fn rules(&self) -> GameRules {
+        GameRules {
+            consolation: self.consolation,
+            payout: self.payout,
+            faces: self.faces,
+            roll: self.roll,
+            exploding: self
+                .exploding
+                .then(|| self.max_bet.unwrap_or(u16::from(self.faces.max()) * 4)),
+        }
+    }
+
+    pub fn costs(&self) -> PowerCosts {
+        PowerCosts {
+            reroll: self.reroll_cost,
+            flip: self.flip_cost,
+        }
+    }
+
+    fn apply_config(
+        &mut self,
+        config: &Config,
+        matches: &ArgMatches,
+    ) -> Result<(), SimulationError> {
+        if unset(matches, "seed") {
+            self.seed = config.simulation.seed;
+        }
+        if unset(matches, "power_availability") {
+            self.power_availability = config.simulation.power_availability;
+        }
+        if unset(matches, "consolation") {
+            self.consolation = config.rules.consolation;
+        }
+        if unset(matches, "payout") {
+            self.payout = config.payout()?;
+        }
+        if unset(matches, "faces") {
+            self.faces = config.faces()?;
+        }
+        if unset(matches, "roll") {
+            self.roll = config.roll()?;
+        }
+        if unset(matches, "exploding") {
+            self.exploding = config.rules.exploding;
+        }
+        if unset(matches, "max_bet") {
+            self.max_bet = config.rules.max_bet;
+        }
+        if unset(matches, "reroll_cost") {
+            self.reroll_cost = config.rules.reroll_cost;
+        }
+        if unset(matches, "flip_cost") {
+            self.flip_cost = config.rules.flip_cost;
+        }
+        if unset(matches, "output") {
+            self.output = config.output.path.clone();
+        }
+        Ok(())
+    }
+}
+
+// Strategies to run alongside the built-in ones, for the subcommands that run
+// strategies.
+#[derive(Clone, Debug, clap::Args)]
+pub struct StrategyArgs {
+    /// Also run `RerollIfLosing` with only this many rerolls per trial, or per
+    /// session with --session.
+    #[arg(long)]
+    pub reroll_budget: Option<u8>,
+
+    /// Also run the decision tree strategy described by this JSON file.
+    #[arg(long)]
+    pub tree: Option<PathBuf>,
+
+    /// Also run the policy table in this file, as saved by `policy-search`.
+    #[arg(long)]
+    pub policy: Option<PathBuf>,
+
+    /// Also run a strategy that rerolls when losing with this probability each
+    /// round, and otherwise does nothing.
+    #[arg(long, value_name = "P")]
+    pub mix: Option<f64>,
+
+    /// Also run a (bet, sum) lookup table evolved over this many generations.
+    #[arg(long, value_name = "GENERATIONS")]
+    pub evolve: Option<u32>,
+
+    /// Tables in each generation with --evolve.
+    #[arg(long, default_value_t = 20)]
+    pub population: u32,
+
+    /// Trials per bet when scoring each table with --evolve.
+    #[arg(long, default_value_t = 10_000)]
+    pub evolve_trials: u64,
+
+    /// Also run a table trained by trying every power this many times on each
+    /// bet and roll.
+    #[arg(long, value_name = "TRIALS_PER_STATE")]
+    pub train: Option<u64>,
+}
+
+impl StrategyArgs {
+    // Every strategy that runs, after the config's selection.
+    pub fn strategies(
+        &self,
+        shared: &SharedArgs,
+        config: &Config,
+    ) -> Result<Vec<Box<dyn Strategy>>, SimulationError> {
+        let rules = shared.rules();
+        let costs = shared.costs();
+        let mut strategies = betting_problem::strategies(&rules, &costs);
+        if let Some(rerolls) = self.reroll_budget {
+            strategies.push(Box::new(BudgetedRerollIfLosing(PowerBudget {
+                rerolls_remaining: rerolls,
+                flips_remaining: 0,
+            })));
+        }
+        if let Some(path) = &self.policy {
+            strategies.push(Box::new(PolicyTable::load(path)?));
+        }
+        if let Some(p) = self.mix {
+            strategies.push(Box::new(MixedStrategy::new(
+                RerollIfLosing(),
+                NoPower(),
+                p,
+            )?));
+        }
+        if let Some(path) = &self.tree {
+            let tree = DTree::load(path)?;
+            tree.check(rules.faces)?;
+            strategies.push(Box::new(tree));
+        }
+        if let Some(trials_per_state) = self.train {
+            let mut rng = rng_from(shared.seed);
+            strategies.push(Box::new(EmpiricalStrategy::train(
+                trials_per_state,
+                &rules,
+                &costs,
+                &mut rng,
+            )?));
+        }
+        if let Some(generations) = self.evolve {
+            let mut rng = rng_from(shared.seed);
+            strategies.push(Box::new(evolve_strategy(
+                &mut rng,
+                generations,
+                self.population,
+                self.evolve_trials,
+                &rules,
+                shared.power_availability,
+                &costs,
+            )?));
+        }
+        for name in &config.strategies.select {
+            if !strategies
+                .iter()
+                .any(|strategy| strategy.name().eq_ignore_ascii_case(name))
+            {
+                return Err(SimulationError::InvalidConfig(format!(
+                    "unknown strategy {:?}",
+                    name
+                )));
+            }
+        }
+        strategies.retain(|strategy| config.selects(strategy.name()));
+        Ok(strategies)
+    }
+
+    fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        if unset(matches, "reroll_budget") {
+            self.reroll_budget = config.strategies.reroll_budget;
+        }
+        if unset(matches, "tree") {
+            self.tree = config.strategies.tree.clone();
+        }
+    }
+}
+
+// Whether the flag `id` was left for the config to fill in.
+fn unset(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) != Some(ValueSource::CommandLine)
+}
+
+pub fn run(mut args: Args, matches: &ArgMatches) -> Result<(), SimulationError> {
+    // A subcommand's own flags are in its matches, and the shared ones in both.
+    let command_matches = matches.subcommand().map_or(matches, |(_, matches)| matches);
+    let config = match &args.shared.config {
+        Some(path) => {
+            let config = Config::from_file(path)?;
+            args.shared.apply_config(&config, command_matches)?;
+            match &mut args.command {
+                None => args.simulate.0.apply_config(&config, matches)?,
+                Some(Command::Simulate(simulate)) => {
+                    simulate.apply_config(&config, command_matches)?
+                }
+                Some(Command::Exact(exact)) => exact.apply_config(&config, command_matches)?,
+                Some(Command::Compare(compare)) => compare.apply_config(&config, command_matches),
+                Some(Command::Tournament(tournament)) => {
+                    tournament.apply_config(&config, command_matches)
+                }
+                Some(Command::PolicySearch(search)) => {
+                    search.apply_config(&config, command_matches)
+                }
+                Some(Command::Record(record)) => record.apply_config(&config, command_matches),
+                Some(Command::Replay(replay)) => replay.apply_config(&config, command_matches),
+                Some(Command::Versus(versus)) => versus.apply_config(&config, command_matches),
+                Some(Command::Repl(repl)) => repl.apply_config(&config, command_matches),
+                Some(
+                    Command::CheckDice
+                    | Command::Optimize(_)
+                    | Command::DecisionTable(_)
+                    | Command::ListStrategies
+                    | Command::Play,
+                ) => {}
+            }
+            config
+        }
+        None => Config::default(),
+    };
+    let shared = &args.shared;
+    shared.costs().check()?;
+    match &args.command {
+        Some(Command::Play) => return play::run(shared),
+        Some(Command::Repl(repl)) => return repl::run(repl, shared, &config),
+        _ => {}
+    }
+    let mut out: Box<dyn Write> = match &shared.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout()),
+    };
+    match &args.command {
+        None => simulate::run(&args.simulate.0, shared, &config, &mut out)?,
+        Some(Command::Simulate(simulate)) => simulate::run(simulate, shared, &config, &mut out)?,
+        Some(Command::Exact(exact)) => exact::run(exact, shared, &config, &mut out)?,
+        Some(Command::Compare(compare)) => compare::run(compare, shared, &config, &mut out)?,
+        Some(Command::CheckDice) => check_dice::run(shared, &mut out)?,
+        Some(Command::Tournament(tournament)) => {
+            tournament::run(tournament, shared, &config, &mut out)?
+        }
+        Some(Command::Optimize(optimize)) => optimize::run(optimize, shared, &mut out)?,
+        Some(Command::PolicySearch(search)) => policy_search::run(search, shared, &mut out)?,
+        Some(Command::Record(record)) => record::run(record, shared, &config, &mut out)?,
+        Some(Command::Replay(replay)) => replay::run(replay, shared, &config, &mut out)?,
+        Some(Command::Versus(versus)) => versus::run(versus, shared, &config, &mut out)?,
+        Some(Command::DecisionTable(table)) => decision_table::run(table, shared, &mut out)?,
+        Some(Command::ListStrategies) => list_strategies::run(shared, &mut out)?,
+        Some(Command::Play | Command::Repl(_)) => unreachable!("handled before opening --output"),
+    }
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn arguments_are_consistent() {
+        Args::command().debug_assert();
+    }
+
+    #[test]
+    fn shared_flags_go_before_or_after_the_subcommand() {
+        for argv in [
+            &[
+                "betting-problem",
+                "--seed",
+                "3",
+                "--faces",
+                "1,2,3",
+                "exact",
+            ][..],
+            &[
+                "betting-problem",
+                "exact",
+                "--seed",
+                "3",
+                "--faces",
+                "1,2,3",
+            ],
+        ] {
+            let args = Args::try_parse_from(argv).unwrap();
+            assert!(matches!(args.command, Some(Command::Exact(_))));
+            assert_eq!(args.shared.seed, Some(3));
+            assert_eq!(args.shared.faces, "1,2,3".parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn subcommands_only_take_their_own_flags() {
+        assert!(Args::try_parse_from(["betting-problem", "check-dice", "--trials", "5"]).is_err());
+        assert!(
+            Args::try_parse_from(["betting-problem", "list-strategies", "--mix", "0.5"]).is_err()
+        );
+        assert!(Args::try_parse_from(["betting-problem", "--trials", "5", "check-dice"]).is_err());
+    }
+}
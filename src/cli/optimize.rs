@@ -0,0 +1,65 @@
+use std::io::Write;
+
+use rand::random;
+
+use betting_problem::optimize::{self, GridCsv, ThresholdReroll};
+use betting_problem::tournament;
+use betting_problem::{Outcome, SimulationError};
+
+use super::SharedArgs;
+
+#[derive(Debug, clap::Args)]
+pub struct OptimizeArgs {
+    /// Rounds per bet for each candidate.
+    #[arg(long, default_value_t = 10_000)]
+    pub candidate_trials: u64,
+}
+
+pub fn run(
+    args: &OptimizeArgs,
+    shared: &SharedArgs,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let seed = shared.seed.unwrap_or_else(random);
+    let grid = optimize::grid_search(
+        &optimize::threshold_grid(),
+        ThresholdReroll,
+        args.candidate_trials,
+        seed,
+        &shared.rules(),
+        shared.power_availability,
+        &shared.costs(),
+    )?;
+    let named: Vec<(String, Outcome)> = grid
+        .iter()
+        .map(|(params, outcome)| (params.to_string(), outcome.clone()))
+        .collect();
+    writeln!(
+        out,
+        "n = {} per candidate, seed = {}\n{}\n{}",
+        args.candidate_trials,
+        seed,
+        tournament::winners_by_bet(&named),
+        GridCsv(&grid)
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn parses_candidate_trials() {
+        let args =
+            Args::try_parse_from(["betting-problem", "optimize", "--candidate-trials", "50"])
+                .unwrap();
+        match args.command {
+            Some(Command::Optimize(optimize)) => assert_eq!(optimize.candidate_trials, 50),
+            command => panic!("parsed {:?}", command),
+        }
+        assert!(Args::try_parse_from(["betting-problem", "optimize", "--trials", "50"]).is_err());
+    }
+}
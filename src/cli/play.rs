@@ -0,0 +1,35 @@
+use std::io;
+
+use betting_problem::play;
+use betting_problem::simulator::rng_from;
+use betting_problem::SimulationError;
+
+use super::SharedArgs;
+
+pub fn run(shared: &SharedArgs) -> Result<(), SimulationError> {
+    let mut rng = rng_from(shared.seed);
+    let stdin = io::stdin();
+    play::play(
+        stdin.lock(),
+        io::stdout(),
+        &shared.rules(),
+        &shared.costs(),
+        &mut rng,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn takes_only_shared_flags() {
+        let args = Args::try_parse_from(["betting-problem", "play", "--reroll-cost", "2"]).unwrap();
+        assert!(matches!(args.command, Some(Command::Play)));
+        assert_eq!(args.shared.costs().reroll, 2);
+        assert!(Args::try_parse_from(["betting-problem", "play", "--trials", "5"]).is_err());
+    }
+}
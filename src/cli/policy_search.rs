@@ -0,0 +1,85 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+use rand::random;
+
+use betting_problem::config::Config;
+use betting_problem::search::{self, PolicyGrid};
+use betting_problem::{Mode, SimulationError};
+
+use super::{unset, SharedArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct PolicySearchArgs {
+    /// Save the table found here, to run later with --policy.
+    #[arg(long)]
+    pub save: Option<PathBuf>,
+
+    /// Rounds simulated per bet when the dice can't be enumerated.
+    #[arg(long, default_value_t = 1_000_000)]
+    pub trials: u64,
+}
+
+impl PolicySearchArgs {
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        if unset(matches, "trials") {
+            self.trials = config.simulation.trials;
+        }
+    }
+}
+
+pub fn run(
+    args: &PolicySearchArgs,
+    shared: &SharedArgs,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let rules = shared.rules();
+    let mode = match rules.check_enumerable() {
+        Ok(()) => Mode::Exact,
+        Err(_) => Mode::MonteCarlo(args.trials),
+    };
+    let seed = shared.seed.unwrap_or_else(random);
+    let (table, outcome) = search::hill_climb(
+        mode,
+        seed,
+        &rules,
+        shared.power_availability,
+        &shared.costs(),
+    )?;
+    write!(
+        out,
+        "{}",
+        PolicyGrid {
+            table: &table,
+            outcome: &outcome,
+        }
+    )?;
+    if let Some(path) = &args.save {
+        table.save(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn parses_where_to_save() {
+        let args =
+            Args::try_parse_from(["betting-problem", "policy-search", "--save", "table.json"])
+                .unwrap();
+        match args.command {
+            Some(Command::PolicySearch(search)) => {
+                assert_eq!(search.save.as_deref(), Some(Path::new("table.json")));
+                assert_eq!(search.trials, 1_000_000);
+            }
+            command => panic!("parsed {:?}", command),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use betting_problem::config::Config;
+use betting_problem::replay::RecordingRollSource;
+use betting_problem::simulator::rng_from;
+use betting_problem::SimulationError;
+
+use super::{replay, SharedArgs, StrategyArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct RecordArgs {
+    pub strategy: String,
+
+    pub bet: u16,
+
+    pub path: PathBuf,
+
+    /// Rounds to play.
+    #[arg(long, default_value_t = 100)]
+    pub rounds: u32,
+
+    #[command(flatten)]
+    pub strategies: StrategyArgs,
+}
+
+impl RecordArgs {
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        self.strategies.apply_config(config, matches);
+    }
+}
+
+pub fn run(
+    args: &RecordArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let rng = rng_from(shared.seed);
+    let mut source = RecordingRollSource::new(rng, shared.rules());
+    replay::play(
+        &args.strategies,
+        shared,
+        config,
+        (&args.strategy, args.bet),
+        &mut source,
+        args.rounds,
+        out,
+    )?;
+    source.recorded().save(&args.path)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn parses_rounds() {
+        let args = Args::try_parse_from([
+            "betting-problem",
+            "record",
+            "NoChange",
+            "7",
+            "rolls.json",
+            "--rounds",
+            "3",
+            "--seed",
+            "1",
+        ])
+        .unwrap();
+        match args.command {
+            Some(Command::Record(record)) => {
+                assert_eq!(record.rounds, 3);
+                assert_eq!(record.bet, 7);
+            }
+            command => panic!("parsed {:?}", command),
+        }
+        assert_eq!(args.shared.seed, Some(1));
+    }
+}
@@ -0,0 +1,52 @@
+use std::io;
+
+use clap::ArgMatches;
+
+use betting_problem::config::Config;
+use betting_problem::simulator::rng_from;
+use betting_problem::SimulationError;
+
+use super::{SharedArgs, StrategyArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct ReplArgs {
+    #[command(flatten)]
+    pub strategies: StrategyArgs,
+}
+
+impl ReplArgs {
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        self.strategies.apply_config(config, matches);
+    }
+}
+
+pub fn run(args: &ReplArgs, shared: &SharedArgs, config: &Config) -> Result<(), SimulationError> {
+    let mut rng = rng_from(shared.seed);
+    let stdin = io::stdin();
+    betting_problem::repl::repl(
+        stdin.lock(),
+        io::stdout(),
+        &args.strategies.strategies(shared, config)?,
+        &shared.rules(),
+        shared.power_availability,
+        &shared.costs(),
+        &mut rng,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn takes_extra_strategies() {
+        let args =
+            Args::try_parse_from(["betting-problem", "repl", "--reroll-budget", "3"]).unwrap();
+        match args.command {
+            Some(Command::Repl(repl)) => assert_eq!(repl.strategies.reroll_budget, Some(3)),
+            command => panic!("parsed {:?}", command),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use betting_problem::config::Config;
+use betting_problem::replay::{self, RecordedDice, RollSource};
+use betting_problem::*;
+
+use super::{SharedArgs, StrategyArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct ReplayArgs {
+    pub strategy: String,
+
+    pub bet: u16,
+
+    pub path: PathBuf,
+
+    #[command(flatten)]
+    pub strategies: StrategyArgs,
+}
+
+impl ReplayArgs {
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        self.strategies.apply_config(config, matches);
+    }
+}
+
+pub fn run(
+    args: &ReplayArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let mut recorded = RecordedDice::load(&args.path)?;
+    play(
+        &args.strategies,
+        shared,
+        config,
+        (&args.strategy, args.bet),
+        &mut recorded,
+        u32::MAX,
+        out,
+    )
+}
+
+// Plays up to `limit` rounds from `source`, stopping early if it runs out.
+pub fn play(
+    strategies: &StrategyArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    (name, bet): (&str, u16),
+    source: &mut dyn RollSource,
+    limit: u32,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let rules = shared.rules();
+    let costs = shared.costs();
+    let strategies = strategies.strategies(shared, config)?;
+    let strategy = find_strategy(&strategies, name)?;
+    let bet = Bet::new(bet, &rules)?;
+    let (mut rounds, mut net) = (0, Gold::ZERO);
+    while rounds < limit {
+        let round = match replay::replay_round(strategy, bet, &rules, &costs, source) {
+            Some(round) => round,
+            None => break,
+        };
+        writeln!(out, "{}", round)?;
+        rounds += 1;
+        net = net.checked_add(round.net())?;
+    }
+    writeln!(out, "{} rounds, {} gold", rounds, net)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn parses_the_strategy_bet_and_file() {
+        let args =
+            Args::try_parse_from(["betting-problem", "replay", "NoChange", "7", "rolls.json"])
+                .unwrap();
+        match args.command {
+            Some(Command::Replay(replay)) => {
+                assert_eq!(replay.strategy, "NoChange");
+                assert_eq!(replay.bet, 7);
+                assert_eq!(replay.path, Path::new("rolls.json"));
+            }
+            command => panic!("parsed {:?}", command),
+        }
+        assert!(Args::try_parse_from([
+            "betting-problem",
+            "replay",
+            "NoChange",
+            "7",
+            "rolls.json",
+            "--rounds",
+            "3"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,853 @@
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use clap::{ArgMatches, FromArgMatches};
+use rand::prelude::*;
+
+use betting_problem::chart::{Cdf, Chart, OutputFormat};
+use betting_problem::checkpoint::Checkpoint;
+use betting_problem::config::Config;
+use betting_problem::dump::{DumpFormat, TrialWriter};
+use betting_problem::exact::exact_fractions;
+use betting_problem::policy::{self, BettingStrategy, PolicyOutcome, WithBet};
+use betting_problem::progress::ProgressBar;
+use betting_problem::provenance::Provenance;
+use betting_problem::regret::{self, RegretTable};
+use betting_problem::session::Session;
+use betting_problem::simulator::{self, rng_from, EstimateOutcome, SimulationBuilder};
+use betting_problem::sizing::{BestEv, Conservative, FractionalKelly, SizingStrategy};
+use betting_problem::tournament;
+use betting_problem::*;
+
+use super::{unset, SharedArgs, StrategyArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct SimulateArgs {
+    #[command(flatten)]
+    pub report: ReportArgs,
+
+    #[command(flatten)]
+    pub strategies: StrategyArgs,
+
+    #[command(flatten)]
+    pub session: SessionArgs,
+
+    /// Rounds simulated per bet.
+    #[arg(long, default_value_t = 1_000_000)]
+    pub trials: u64,
+
+    /// Show a 95% bootstrap confidence interval for each strategy at --bet,
+    /// from this many resamples of --trials rounds.
+    #[arg(long, requires = "bet", conflicts_with_all = ["distribution", "fractions"])]
+    pub bootstrap: Option<u32>,
+
+    /// Chart the sampled chance of at most each gold value at --bet instead
+    /// of the mean.
+    #[arg(
+        long,
+        requires = "bet",
+        conflicts_with_all = ["exact", "antithetic", "distribution", "bootstrap", "fractions"]
+    )]
+    pub cdf: bool,
+
+    /// Enumerate every roll instead of sampling.
+    #[arg(long, conflicts_with = "exploding")]
+    pub exact: bool,
+
+    /// Sample rounds in mirrored pairs, which converges faster.
+    #[arg(long, conflicts_with_all = ["exact", "fractions"])]
+    pub antithetic: bool,
+
+    /// Print sample rounds for each bet before each strategy's table.
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Rounds printed per bet with --verbose.
+    #[arg(long, default_value_t = 20)]
+    pub trace_rounds: u64,
+
+    /// Write every trial of the selected strategies and bets to this .csv or
+    /// .jsonl file.
+    #[arg(long)]
+    pub dump_trials: Option<PathBuf>,
+
+    /// Sample each bet only until its 95% confidence interval is within this
+    /// of the mean, up to --trials.
+    #[arg(long, conflicts_with_all = ["exact", "antithetic", "progress", "regret", "winners"])]
+    pub precision: Option<f64>,
+
+    /// Don't show a progress bar.
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// Print the running averages every this many trials.
+    #[arg(long, conflicts_with_all = ["exact", "antithetic"])]
+    pub progress: Option<u64>,
+
+    /// Save progress to this file as the simulation runs, so it can be
+    /// continued with --resume.
+    #[arg(long, conflicts_with_all = ["exact", "antithetic", "precision", "progress"])]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Continue the simulation saved in this file up to --trials, saving
+    /// progress back to it unless --checkpoint says otherwise.
+    #[arg(long, conflicts_with_all = ["exact", "antithetic", "precision", "progress"])]
+    pub resume: Option<PathBuf>,
+
+    /// Print a config file with every key at its default, then exit.
+    #[arg(long)]
+    pub print_default_config: bool,
+}
+
+// What to report about the strategies, shared with `exact`.
+#[derive(Clone, Debug, clap::Args)]
+pub struct ReportArgs {
+    /// Evaluate each strategy as a complete policy that always makes this bet.
+    #[arg(long)]
+    pub bet: Option<u16>,
+
+    /// Show the probability of each gold value at --bet instead of the mean.
+    #[arg(long, requires = "bet")]
+    pub distribution: bool,
+
+    /// Enumerate every roll and give each expected gold as an exact fraction.
+    #[arg(long, conflicts_with = "distribution")]
+    pub fractions: bool,
+
+    /// Draw every strategy's EV by bet to this .svg or .png file.
+    #[cfg(feature = "plot")]
+    #[arg(long)]
+    pub plot: Option<PathBuf>,
+
+    /// Print outcomes as a table, a bar chart of each bet's EV, or a LaTeX
+    /// table.
+    #[arg(long, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+
+    /// Width of the longest bar with --format chart.
+    #[arg(long, default_value_t = 50)]
+    pub chart_width: usize,
+
+    /// Also print how much each strategy falls short of the best possible play
+    /// on each bet.
+    #[arg(long)]
+    pub regret: bool,
+
+    /// Also print which strategy earns the most at each bet, and by how much
+    /// it beats the runner-up.
+    #[arg(long)]
+    pub winners: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SessionArgs {
+    /// Simulate multi-round sessions instead of single rounds.
+    #[arg(long)]
+    pub session: bool,
+
+    /// Rounds played per session.
+    #[arg(long, default_value_t = 100)]
+    pub rounds: u32,
+
+    /// Number of sessions to simulate per bet.
+    #[arg(long, default_value_t = 10_000)]
+    pub sessions: u64,
+
+    /// Gold each session starts with.
+    #[arg(long, default_value_t = 100)]
+    pub bankroll: i64,
+
+    /// Wager the bet from the bankroll each round.
+    #[arg(long)]
+    pub stake: bool,
+
+    /// Compare bet-sizing strategies instead of fixed bets.
+    #[arg(long)]
+    pub sizing: bool,
+}
+
+// Every flag at its default, as if `simulate` were run bare.
+impl Default for SimulateArgs {
+    fn default() -> Self {
+        let command =
+            <super::SharedArgs as clap::Args>::augment_args(clap::Command::new("simulate"));
+        let command = <Self as clap::Args>::augment_args(command);
+        Self::from_arg_matches(&command.get_matches_from(["simulate"]))
+            .expect("the defaults are valid")
+    }
+}
+
+impl SimulateArgs {
+    pub fn apply_config(
+        &mut self,
+        config: &Config,
+        matches: &ArgMatches,
+    ) -> Result<(), SimulationError> {
+        if unset(matches, "trials") {
+            self.trials = config.simulation.trials;
+        }
+        if unset(matches, "dump_trials") {
+            self.dump_trials = config.output.dump_trials.clone();
+        }
+        self.report.apply_config(config, matches)?;
+        self.strategies.apply_config(config, matches);
+        Ok(())
+    }
+}
+
+impl ReportArgs {
+    pub fn apply_config(
+        &mut self,
+        config: &Config,
+        matches: &ArgMatches,
+    ) -> Result<(), SimulationError> {
+        if unset(matches, "format") {
+            self.format = config.output_format()?;
+        }
+        Ok(())
+    }
+}
+
+pub fn run(
+    args: &SimulateArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    if args.print_default_config {
+        write!(out, "{}", Config::default().to_toml())?;
+        return Ok(());
+    }
+    let report = &args.report;
+    let rules = shared.rules();
+    config.bets(&rules)?;
+    if let Some(bet) = report.bet {
+        Bet::new(bet, &rules)?;
+    }
+    let strategies = args.strategies.strategies(shared, config)?;
+    // Every sampled result below draws from this one stream, so --seed
+    // repeats the whole run.
+    let mut rng = rng_from(shared.seed);
+    if args.session.session {
+        return run_sessions(&args.session, shared, &strategies, &mut rng, out);
+    }
+
+    if args.checkpoint.is_some() || args.resume.is_some() {
+        return run_checkpointed(args, shared, config, &strategies, out);
+    }
+
+    let mode = if args.exact || report.fractions {
+        Mode::Exact
+    } else if args.antithetic {
+        Mode::Antithetic(args.trials)
+    } else {
+        Mode::MonteCarlo(args.trials)
+    };
+    let provenance = Provenance::new(
+        mode,
+        shared.seed,
+        &rules,
+        shared.power_availability,
+        &shared.costs(),
+        SystemTime::now(),
+    );
+    if report.format == OutputFormat::Latex {
+        for (name, value) in provenance.parameters() {
+            writeln!(out, "% {} = {}", name, value)?;
+        }
+    } else {
+        writeln!(out, "{}", provenance)?;
+    }
+    if let Some(path) = &args.dump_trials {
+        dump_trials(args, shared, path, config, &strategies)?;
+    }
+    if let (Some(bet), Some(resamples)) = (report.bet, args.bootstrap) {
+        let bet = Bet::new(bet, &rules)?;
+        for strategy in &strategies {
+            let (low, high) = strategy.bootstrap_confidence_interval(
+                args.trials,
+                bet,
+                resamples,
+                0.05,
+                &rules,
+                shared.power_availability,
+                &shared.costs(),
+                &mut rng,
+            )?;
+            writeln!(out, "{}: {:.3}..{:.3}", strategy.name(), low, high)?;
+        }
+        return Ok(());
+    }
+    if report.fractions {
+        for strategy in &strategies {
+            writeln!(out, "{}:", strategy.name())?;
+            writeln!(out, "Bet | Exp")?;
+            writeln!(out, "--- | ---")?;
+            for (bet, ev) in exact_fractions(
+                strategy.as_ref(),
+                &rules,
+                shared.power_availability,
+                &shared.costs(),
+            )? {
+                writeln!(out, "{:>3} | {} ({:.4})", bet, ev, ev.to_f64())?;
+            }
+        }
+        return Ok(());
+    }
+    if let (Some(bet), true) = (report.bet, args.cdf) {
+        let bet = Bet::new(bet, &rules)?;
+        for strategy in &strategies {
+            let points = strategy.empirical_cdf(
+                bet,
+                args.trials,
+                &rules,
+                shared.power_availability,
+                &shared.costs(),
+                &mut rng,
+            )?;
+            writeln!(
+                out,
+                "{}:\n{}",
+                strategy.name(),
+                Cdf {
+                    points: &points,
+                    width: report.chart_width,
+                }
+            )?;
+        }
+        return Ok(());
+    }
+    if let (Some(bet), true) = (report.bet, report.distribution) {
+        let bet = Bet::new(bet, &rules)?;
+        for strategy in &strategies {
+            let distribution = strategy.gold_distribution(
+                bet,
+                mode,
+                &rules,
+                shared.power_availability,
+                &shared.costs(),
+                &mut rng,
+            )?;
+            writeln!(
+                out,
+                "{}:\n{}",
+                strategy.name(),
+                GoldDistribution(distribution)
+            )?;
+        }
+        return Ok(());
+    }
+    if let Some(bet) = report.bet {
+        let bet = Bet::new(bet, &rules)?;
+        let outcome = strategies
+            .iter()
+            .map(|strategy| {
+                let policy = WithBet {
+                    strategy: &**strategy,
+                    bet,
+                };
+                let gold = policy::expected_gold(
+                    &policy,
+                    mode,
+                    &rules,
+                    shared.power_availability,
+                    &shared.costs(),
+                    &mut rng,
+                )?;
+                Ok((policy.label(&rules), gold))
+            })
+            .collect::<Result<_, SimulationError>>()?;
+        writeln!(out, "{}", PolicyOutcome(outcome))?;
+        return Ok(());
+    }
+    let (mut total_elapsed, mut total_rounds) = (Duration::ZERO, 0);
+    let mut outcomes = Vec::new();
+    for strategy in &strategies {
+        if args.verbose {
+            trace(args, shared, &**strategy, config, &mut rng, out)?;
+        }
+        if let Some(epsilon) = args.precision {
+            let (min, max) = config.bets(&rules)?;
+            let estimates = rules
+                .bets()
+                .filter(|&bet| min <= bet && bet <= max)
+                .map(|bet| {
+                    let estimate = simulator::run_until_precise(
+                        &**strategy,
+                        bet,
+                        epsilon,
+                        args.trials,
+                        &rules,
+                        shared.power_availability,
+                        &shared.costs(),
+                        &mut rng,
+                    )?;
+                    Ok((bet, estimate))
+                })
+                .collect::<Result<_, SimulationError>>()?;
+            writeln!(out, "{}:\n{}", strategy.name(), EstimateOutcome(estimates))?;
+            continue;
+        }
+        let started = Instant::now();
+        let outcome = match args.progress {
+            Some(report_every) => strategy.avg_outcome_with_progress(
+                args.trials,
+                report_every,
+                &rules,
+                shared.power_availability,
+                &shared.costs(),
+                &mut |n, outcome| eprintln!("{} after {} trials:\n{}", strategy.name(), n, outcome),
+                &mut rng,
+            )?,
+            None => match mode {
+                Mode::MonteCarlo(trials) if !args.quiet && io::stderr().is_terminal() => {
+                    let bets = rules.bets().count() as u64;
+                    let bar = ProgressBar::new(strategy.name(), bets, trials);
+                    let first = rules.min_bet().value();
+                    let outcome = strategy.avg_outcome_reporting(
+                        trials,
+                        &rules,
+                        shared.power_availability,
+                        &shared.costs(),
+                        &mut |bet, done| bar.update((bet.value() - first).into(), bet, done),
+                        &mut rng,
+                    );
+                    bar.finish();
+                    outcome?
+                }
+                _ => {
+                    let (min, max) = config.bets(&rules)?;
+                    SimulationBuilder::default()
+                        .mode(mode)
+                        .seed(rng.gen())
+                        .rules(rules)
+                        .bet_range(min.value()..=max.value())
+                        .power_availability(shared.power_availability)
+                        .costs(shared.costs())
+                        .run(&**strategy)?
+                }
+            },
+        };
+        let elapsed = started.elapsed();
+        let rounds = match mode {
+            Mode::Exact if strategy.is_deterministic() => None,
+            Mode::Exact => Some(1_000_000),
+            Mode::MonteCarlo(trials) | Mode::Antithetic(trials) => Some(trials),
+        }
+        .map(|trials| trials * outcome.bets.len() as u64);
+        total_elapsed += elapsed;
+        total_rounds += rounds.unwrap_or(0);
+        let outcome = config.restrict(&outcome, &rules)?;
+        writeln!(
+            out,
+            "{}:\n{}{}\n",
+            strategy.name(),
+            match report.format {
+                OutputFormat::Table => outcome.to_string(),
+                OutputFormat::Chart => Chart {
+                    outcome: &outcome,
+                    width: report.chart_width,
+                }
+                .to_string(),
+                OutputFormat::Latex => outcome.to_latex(
+                    strategy.name(),
+                    &format!(
+                        "tab:{}",
+                        strategy
+                            .name()
+                            .to_lowercase()
+                            .split(|c: char| !c.is_ascii_alphanumeric())
+                            .filter(|word| !word.is_empty())
+                            .collect::<Vec<_>>()
+                            .join("-")
+                    ),
+                ),
+            },
+            throughput(elapsed, rounds)
+        )?;
+        outcomes.push((strategy.name(), outcome));
+    }
+    if strategies.len() > 1 {
+        writeln!(
+            out,
+            "Total: {}",
+            throughput(
+                total_elapsed,
+                Some(total_rounds).filter(|&rounds| rounds > 0)
+            )
+        )?;
+    }
+    let named: Vec<(String, Outcome)> = outcomes
+        .iter()
+        .map(|(name, outcome)| (name.to_string(), outcome.clone()))
+        .collect();
+    #[cfg(feature = "plot")]
+    if let Some(path) = &report.plot {
+        betting_problem::plot::plot_outcomes(&named, path)?;
+    }
+    if report.winners {
+        writeln!(out, "\nWinners:\n{}", tournament::winners_by_bet(&named))?;
+    }
+    if report.regret {
+        let optimal = config.restrict(
+            &regret::optimal_outcome(&rules, shared.power_availability, &shared.costs())?,
+            &rules,
+        )?;
+        let strategies: Vec<_> = outcomes
+            .iter()
+            .map(|(name, outcome)| (*name, outcome))
+            .collect();
+        writeln!(
+            out,
+            "\nRegret:\n{}",
+            RegretTable {
+                bets: optimal.bets.clone(),
+                regrets: regret::regret_analysis(&strategies, &optimal),
+            }
+        )?;
+    }
+    Ok(())
+}
+
+// Rules, power availability, and costs come from the checkpoint when resuming,
+// and from the arguments otherwise.
+fn run_checkpointed(
+    args: &SimulateArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    strategies: &[Box<dyn Strategy>],
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let (mut checkpoint, path) = match (&args.resume, &args.checkpoint) {
+        (Some(resume), checkpoint) => (
+            Checkpoint::load(resume)?,
+            checkpoint.as_ref().unwrap_or(resume),
+        ),
+        (None, Some(path)) => (
+            Checkpoint::new(
+                shared.seed.unwrap_or_else(|| thread_rng().gen()),
+                &shared.rules(),
+                shared.power_availability,
+                &shared.costs(),
+            )?,
+            path,
+        ),
+        (None, None) => unreachable!("only called with --checkpoint or --resume"),
+    };
+    let rules = checkpoint.rules()?;
+    writeln!(
+        out,
+        "{}",
+        Provenance::new(
+            Mode::MonteCarlo(args.trials),
+            Some(checkpoint.seed),
+            &rules,
+            checkpoint.power_availability,
+            &checkpoint.costs()?,
+            SystemTime::now(),
+        )
+    )?;
+    for strategy in strategies {
+        let outcome = checkpoint.run(&**strategy, args.trials, path)?;
+        writeln!(
+            out,
+            "{}:\n{}",
+            strategy.name(),
+            config.restrict(&outcome, &rules)?
+        )?;
+    }
+    Ok(())
+}
+
+// Like `1.234s, 8912345 rounds/s`, or just the time for exact solutions.
+fn throughput(elapsed: Duration, rounds: Option<u64>) -> String {
+    let seconds = elapsed.as_secs_f64();
+    match rounds {
+        Some(rounds) => format!("{:.3}s, {:.0} rounds/s", seconds, rounds as f64 / seconds),
+        None => format!("{:.3}s", seconds),
+    }
+}
+
+fn dump_trials(
+    args: &SimulateArgs,
+    shared: &SharedArgs,
+    path: &Path,
+    config: &Config,
+    strategies: &[Box<dyn Strategy>],
+) -> Result<(), SimulationError> {
+    let rules = shared.rules();
+    let costs = shared.costs();
+    check_probability(shared.power_availability)?;
+    let (min, max) = match args.report.bet {
+        Some(bet) => {
+            let bet = Bet::new(bet, &rules)?;
+            (bet, bet)
+        }
+        None => config.bets(&rules)?,
+    };
+    let mut rng = rng_from(shared.seed);
+    let parameters = Provenance::new(
+        Mode::MonteCarlo(args.trials),
+        shared.seed,
+        &rules,
+        shared.power_availability,
+        &costs,
+        SystemTime::now(),
+    )
+    .parameters();
+    let format = DumpFormat::from_path(path)?;
+    let mut writer = TrialWriter::new(BufWriter::new(File::create(path)?), format, &parameters)?;
+    for strategy in strategies {
+        writer.dump(
+            &**strategy,
+            rules.bets().filter(|&bet| min <= bet && bet <= max),
+            args.trials,
+            &rules,
+            shared.power_availability,
+            &costs,
+            &mut rng,
+        )?;
+    }
+    writer.finish()
+}
+
+// Prints `args.trace_rounds` freshly played rounds of `strategy` for each bet.
+fn trace(
+    args: &SimulateArgs,
+    shared: &SharedArgs,
+    strategy: &dyn Strategy,
+    config: &Config,
+    rng: &mut dyn RngCore,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let rules = shared.rules();
+    let costs = shared.costs();
+    check_probability(shared.power_availability)?;
+    let (min, max) = config.bets(&rules)?;
+    writeln!(out, "{} sample rounds:", strategy.name())?;
+    for bet in rules.bets().filter(|&bet| min <= bet && bet <= max) {
+        for _ in 0..args.trace_rounds {
+            writeln!(
+                out,
+                "{}",
+                strategy.round(bet, &rules, shared.power_availability, &costs, rng)
+            )?;
+        }
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+fn run_sessions(
+    args: &SessionArgs,
+    shared: &SharedArgs,
+    strategies: &[Box<dyn Strategy>],
+    rng: &mut dyn RngCore,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let session = Session {
+        bankroll: Gold(check_gold("bankroll", args.bankroll)?),
+        rounds: args.rounds,
+        stake: args.stake,
+        rules: shared.rules(),
+        power_availability: shared.power_availability,
+        costs: shared.costs(),
+    };
+    session.check()?;
+    if args.sessions == 0 {
+        return Err(SimulationError::InvalidTrialCount);
+    }
+    writeln!(
+        out,
+        "sessions = {}, rounds = {}, bankroll = {}{}",
+        args.sessions,
+        args.rounds,
+        args.bankroll,
+        if args.stake { ", staked" } else { "" }
+    )?;
+    if args.sizing {
+        return run_sizing(args, &session, strategies, rng, out);
+    }
+    for strategy in strategies {
+        writeln!(
+            out,
+            "{}:\n{}",
+            strategy.name(),
+            session.outcome(&**strategy, args.sessions, rng)?
+        )?;
+    }
+    Ok(())
+}
+
+fn run_sizing(
+    args: &SessionArgs,
+    session: &Session,
+    strategies: &[Box<dyn Strategy>],
+    rng: &mut dyn RngCore,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let conservative = Conservative {
+        threshold: Gold(args.bankroll / 2),
+    };
+    let kelly = FractionalKelly { fraction: 0.5 };
+    let sizings: &[&dyn SizingStrategy] = &[&BestEv, &conservative, &kelly];
+    for strategy in strategies {
+        writeln!(
+            out,
+            "{}:\n{}",
+            strategy.name(),
+            session.compare_sizing(&**strategy, sizings, args.sessions, rng)?
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+    use super::*;
+
+    #[test]
+    fn no_subcommand_is_simulate() {
+        let args = Args::try_parse_from([
+            "betting-problem",
+            "--trials",
+            "5",
+            "--bet",
+            "7",
+            "--distribution",
+            "--session",
+            "--stake",
+            "--seed",
+            "9",
+        ])
+        .unwrap();
+        assert!(args.command.is_none());
+        assert_eq!(args.simulate.0.trials, 5);
+        assert_eq!(args.simulate.0.report.bet, Some(7));
+        assert!(args.simulate.0.report.distribution);
+        assert!(args.simulate.0.session.session && args.simulate.0.session.stake);
+        assert_eq!(args.shared.seed, Some(9));
+    }
+
+    #[test]
+    fn simulate_takes_the_same_flags() {
+        let args = Args::try_parse_from([
+            "betting-problem",
+            "simulate",
+            "--antithetic",
+            "--mix",
+            "0.5",
+        ])
+        .unwrap();
+        match args.command {
+            Some(Command::Simulate(simulate)) => {
+                assert!(simulate.antithetic);
+                assert_eq!(simulate.strategies.mix, Some(0.5));
+                assert_eq!(simulate.trials, 1_000_000);
+            }
+            command => panic!("parsed {:?}", command),
+        }
+    }
+
+    #[test]
+    fn conflicting_modes_are_rejected() {
+        for argv in [
+            &["betting-problem", "--exact", "--antithetic"][..],
+            &["betting-problem", "--bootstrap", "10"],
+            &["betting-problem", "--bet", "7", "--cdf", "--exact"],
+            &["betting-problem", "--exact", "--exploding"],
+        ] {
+            assert!(Args::try_parse_from(argv).is_err(), "{:?}", argv);
+        }
+    }
+
+    #[test]
+    fn defaults_match_a_bare_run() {
+        let bare = Args::try_parse_from(["betting-problem"])
+            .unwrap()
+            .simulate
+            .0;
+        let default = SimulateArgs::default();
+        assert_eq!(default.trials, bare.trials);
+        assert_eq!(default.trace_rounds, bare.trace_rounds);
+        assert_eq!(default.session.sessions, bare.session.sessions);
+        assert_eq!(default.report.chart_width, bare.report.chart_width);
+    }
+
+    #[test]
+    fn bets_are_checked_at_both_ends_of_the_range() {
+        let run_at = |bet: &str| {
+            let argv = [
+                "betting-problem",
+                "--trials",
+                "100",
+                "--seed",
+                "1",
+                "--bet",
+                bet,
+            ];
+            let args = Args::try_parse_from(argv).unwrap();
+            let mut out = Vec::new();
+            let result = run(&args.simulate.0, &args.shared, &Config::default(), &mut out);
+            (result, out)
+        };
+        for bet in ["1", "13"] {
+            let (result, out) = run_at(bet);
+            assert!(matches!(
+                result,
+                Err(SimulationError::InvalidBet {
+                    min: 2,
+                    max: 12,
+                    ..
+                })
+            ));
+            assert!(out.is_empty());
+        }
+        for bet in ["2", "12"] {
+            let (result, out) = run_at(bet);
+            result.unwrap();
+            assert!(!out.is_empty());
+        }
+    }
+
+    #[test]
+    fn zero_trials_is_an_error() {
+        for argv in [
+            &["betting-problem", "--trials", "0"][..],
+            &[
+                "betting-problem",
+                "--trials",
+                "0",
+                "--bet",
+                "7",
+                "--distribution",
+            ],
+            &[
+                "betting-problem",
+                "--trials",
+                "0",
+                "--session",
+                "--sessions",
+                "0",
+            ],
+        ] {
+            let args = Args::try_parse_from(argv).unwrap();
+            let result = run(
+                &args.simulate.0,
+                &args.shared,
+                &Config::default(),
+                &mut Vec::new(),
+            );
+            assert!(
+                matches!(result, Err(SimulationError::InvalidTrialCount)),
+                "{:?}",
+                argv
+            );
+        }
+    }
+}
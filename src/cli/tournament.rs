@@ -0,0 +1,157 @@
+use std::io::Write;
+use std::time::SystemTime;
+
+use clap::ArgMatches;
+use rand::prelude::*;
+
+use betting_problem::compare;
+use betting_problem::config::Config;
+use betting_problem::policy::WithBet;
+use betting_problem::provenance::{Provenance, RunResult};
+use betting_problem::simulator::SimulationBuilder;
+use betting_problem::tournament::{self, Contender};
+use betting_problem::*;
+
+use super::{unset, SharedArgs, StrategyArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct TournamentArgs {
+    /// Print the leaderboard as JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Rounds simulated per bet, and per match between contenders.
+    #[arg(long, default_value_t = 1_000_000)]
+    pub trials: u64,
+
+    /// Find each contender's best bet by enumerating every roll instead of
+    /// sampling.
+    #[arg(long)]
+    pub exact: bool,
+
+    /// Sample rounds in mirrored pairs, which converges faster.
+    #[arg(long, conflicts_with = "exact")]
+    pub antithetic: bool,
+
+    #[command(flatten)]
+    pub strategies: StrategyArgs,
+}
+
+impl TournamentArgs {
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        if unset(matches, "trials") {
+            self.trials = config.simulation.trials;
+        }
+        self.strategies.apply_config(config, matches);
+    }
+}
+
+pub fn run(
+    args: &TournamentArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let rules = shared.rules();
+    let costs = shared.costs();
+    config.bets(&rules)?;
+    let strategies = args.strategies.strategies(shared, config)?;
+    let mode = if args.exact {
+        Mode::Exact
+    } else if args.antithetic {
+        Mode::Antithetic(args.trials)
+    } else {
+        Mode::MonteCarlo(args.trials)
+    };
+    let started = SystemTime::now();
+    let seed = shared.seed.unwrap_or_else(|| thread_rng().gen());
+    let simulation = SimulationBuilder::default()
+        .mode(mode)
+        .seed(seed)
+        .rules(rules)
+        .power_availability(shared.power_availability)
+        .costs(costs);
+    let contenders = strategies
+        .iter()
+        .map(|strategy| {
+            let outcome = simulation.run(&**strategy)?;
+            Ok(Contender::best(
+                strategy.name(),
+                &config.restrict(&outcome, &rules)?,
+            ))
+        })
+        .collect::<Result<_, SimulationError>>()?;
+    let find = |contender: &Contender| {
+        find_strategy(&strategies, &contender.name).expect("every contender is a strategy")
+    };
+    let leaderboard = tournament::rank(contenders, |a, b| {
+        compare::compare_policies(
+            &WithBet {
+                strategy: find(a),
+                bet: a.bet,
+            },
+            &WithBet {
+                strategy: find(b),
+                bet: b.bet,
+            },
+            args.trials,
+            seed,
+            &rules,
+            shared.power_availability,
+            &costs,
+        )
+    })?;
+    let provenance = Provenance::new(
+        mode,
+        Some(seed),
+        &rules,
+        shared.power_availability,
+        &costs,
+        started,
+    );
+    if args.json {
+        let result = RunResult {
+            provenance,
+            results: leaderboard,
+        };
+        writeln!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&result).expect("a leaderboard is valid JSON")
+        )?;
+    } else {
+        writeln!(out, "{}\n{}", provenance, leaderboard)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn parses_the_mode_and_json() {
+        let args = Args::try_parse_from([
+            "betting-problem",
+            "tournament",
+            "--json",
+            "--trials",
+            "10",
+            "--antithetic",
+        ])
+        .unwrap();
+        match args.command {
+            Some(Command::Tournament(tournament)) => {
+                assert!(tournament.json && tournament.antithetic && !tournament.exact);
+                assert_eq!(tournament.trials, 10);
+            }
+            command => panic!("parsed {:?}", command),
+        }
+        assert!(
+            Args::try_parse_from(["betting-problem", "tournament", "--exact", "--antithetic"])
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::io::Write;
+
+use clap::ArgMatches;
+
+use betting_problem::config::Config;
+use betting_problem::simulator::rng_from;
+use betting_problem::versus::{versus, VersusTable};
+use betting_problem::*;
+
+use super::{unset, SharedArgs, StrategyArgs};
+
+#[derive(Debug, clap::Args)]
+pub struct VersusArgs {
+    pub a: String,
+
+    pub b: String,
+
+    /// The first strategy's bet; without one, every pairing of bets.
+    pub bet_a: Option<u16>,
+
+    /// The second strategy's bet, if not the same as the first's.
+    pub bet_b: Option<u16>,
+
+    /// Give both players the same dice, so only their powers differ.
+    #[arg(long)]
+    pub mirror: bool,
+
+    /// Rounds played for each pairing of bets.
+    #[arg(long, default_value_t = 1_000_000)]
+    pub trials: u64,
+
+    #[command(flatten)]
+    pub strategies: StrategyArgs,
+}
+
+impl VersusArgs {
+    pub fn apply_config(&mut self, config: &Config, matches: &ArgMatches) {
+        if unset(matches, "trials") {
+            self.trials = config.simulation.trials;
+        }
+        self.strategies.apply_config(config, matches);
+    }
+}
+
+pub fn run(
+    args: &VersusArgs,
+    shared: &SharedArgs,
+    config: &Config,
+    out: &mut dyn Write,
+) -> Result<(), SimulationError> {
+    let rules = shared.rules();
+    let strategies = args.strategies.strategies(shared, config)?;
+    let (a, b) = (
+        find_strategy(&strategies, &args.a)?,
+        find_strategy(&strategies, &args.b)?,
+    );
+    let pairings: Vec<(Bet, Bet)> = match (args.bet_a, args.bet_b) {
+        (Some(bet_a), bet_b) => vec![(
+            Bet::new(bet_a, &rules)?,
+            Bet::new(bet_b.unwrap_or(bet_a), &rules)?,
+        )],
+        (None, _) => rules
+            .bets()
+            .flat_map(|bet_a| rules.bets().map(move |bet_b| (bet_a, bet_b)))
+            .collect(),
+    };
+    let mut rng = rng_from(shared.seed);
+    let records = pairings
+        .into_iter()
+        .map(|bets| {
+            versus(
+                (a, b),
+                bets,
+                args.trials,
+                args.mirror,
+                &mut rng,
+                &rules,
+                shared.power_availability,
+                &shared.costs(),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    write!(
+        out,
+        "{} v {}:\n{}",
+        a.name(),
+        b.name(),
+        VersusTable(&records)
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::super::{Args, Command};
+
+    #[test]
+    fn parses_both_bets_and_mirror() {
+        let args = Args::try_parse_from([
+            "betting-problem",
+            "versus",
+            "NoChange",
+            "RerollIfLosing",
+            "7",
+            "8",
+            "--mirror",
+        ])
+        .unwrap();
+        match args.command {
+            Some(Command::Versus(versus)) => {
+                assert_eq!((versus.bet_a, versus.bet_b), (Some(7), Some(8)));
+                assert!(versus.mirror);
+                assert_eq!(versus.trials, 1_000_000);
+            }
+            command => panic!("parsed {:?}", command),
+        }
+    }
+}
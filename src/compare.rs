@@ -0,0 +1,152 @@
+use std::cmp::Ordering;
+
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+
+use crate::policy::{BettingStrategy, WithBet};
+use crate::stats::{normal_quantile, RunningStats};
+use crate::{check_probability, Bet, GameRules, Outcome, PowerCosts, SimulationError, Strategy};
+
+// The paired difference in gold between two strategies, `a - b`.
+#[derive(Clone, Copy, Debug)]
+pub struct Comparison {
+    pub mean_difference: f64,
+    pub std_error: f64,
+}
+
+impl Comparison {
+    // Whether `a` beats `b` by more than noise, at about 95% confidence.
+    pub fn is_significant(&self) -> bool {
+        self.mean_difference > 1.96 * self.std_error
+    }
+}
+
+// Whether `a` earns significantly more (`Greater`) or less (`Less`) than `b`
+// at each bet, by a two-sided z-test at level `alpha` on two independent
+// samples of `n` rounds each. Bets where neither sampled its spread are never
+// significant, and with no spread at all any difference is.
+pub fn compare_significance(
+    a: &Outcome,
+    b: &Outcome,
+    n: u64,
+    alpha: f64,
+) -> Result<Vec<Option<Ordering>>, SimulationError> {
+    if a.bets != b.bets {
+        return Err(SimulationError::MismatchedBets);
+    }
+    if n == 0 {
+        return Err(SimulationError::InvalidTrialCount);
+    }
+    let critical = normal_quantile(1.0 - check_probability(alpha)? / 2.0);
+    Ok((0..a.bets.len())
+        .map(|i| {
+            let std_error = ((a.std_dev[i].powi(2) + b.std_dev[i].powi(2)) / n as f64).sqrt();
+            let difference = a.ev[i] - b.ev[i];
+            if std_error.is_nan() || difference.abs() <= critical * std_error {
+                None
+            } else {
+                difference.partial_cmp(&0.0)
+            }
+        })
+        .collect())
+}
+
+// Plays `a` and `b` against the same rolls, so that the noise common to both
+// cancels out of their difference. Each trial seeds a fresh RNG for both
+// strategies from one master RNG, which keeps every draw, including rerolls,
+// in lockstep for as long as the two strategies make the same draws.
+#[allow(clippy::too_many_arguments)]
+pub fn compare_strategies<A: Strategy + ?Sized, B: Strategy + ?Sized>(
+    a: &A,
+    b: &B,
+    bet: Bet,
+    trials: u64,
+    seed: u64,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+) -> Result<Comparison, SimulationError> {
+    compare_policies(
+        &WithBet { strategy: a, bet },
+        &WithBet { strategy: b, bet },
+        trials,
+        seed,
+        rules,
+        power_availability,
+        costs,
+    )
+}
+
+// Like `compare_strategies`, but each policy makes its own bet on the shared
+// rolls.
+pub fn compare_policies<A: BettingStrategy + ?Sized, B: BettingStrategy + ?Sized>(
+    a: &A,
+    b: &B,
+    trials: u64,
+    seed: u64,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+) -> Result<Comparison, SimulationError> {
+    if trials < 2 {
+        return Err(SimulationError::InvalidTrialCount);
+    }
+    check_probability(power_availability)?;
+    let (bet_a, bet_b) = (a.choose_bet(rules), b.choose_bet(rules));
+    let mut master = SmallRng::seed_from_u64(seed);
+    let mut differences = RunningStats::default();
+    for _ in 0..trials {
+        let trial_seed = master.gen();
+        let gold_a = a.outcome(
+            bet_a,
+            rules,
+            power_availability,
+            costs,
+            &mut SmallRng::seed_from_u64(trial_seed),
+        );
+        let gold_b = b.outcome(
+            bet_b,
+            rules,
+            power_availability,
+            costs,
+            &mut SmallRng::seed_from_u64(trial_seed),
+        );
+        differences.push(f64::from(gold_a - gold_b));
+    }
+    Ok(Comparison {
+        mean_difference: differences.mean(),
+        std_error: differences.std_dev() / (trials as f64).sqrt(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AlwaysReroll, NoPower};
+
+    // A reroll at bet 2 always wins, so the only difference is its cost: a
+    // large mean with no spread at all, which the textbook formula for the
+    // variance can't tell from rounding error.
+    #[test]
+    fn a_constant_difference_has_no_error() {
+        let rules = GameRules::default();
+        let bet = Bet::new(2, &rules).unwrap();
+        let costs = PowerCosts {
+            reroll: 999_999_937,
+            flip: 0,
+        };
+        let comparison = compare_strategies(
+            &AlwaysReroll(),
+            &NoPower(),
+            bet,
+            1_000,
+            3,
+            &rules,
+            1.0,
+            &costs,
+        )
+        .unwrap();
+        assert_eq!(comparison.mean_difference, -999_999_937.0);
+        assert_eq!(comparison.std_error, 0.0);
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::chart::OutputFormat;
+use crate::{Bet, Faces, GameRules, Outcome, PayoutRule, PowerCosts, RollMode, SimulationError};
+
+// A whole experiment described in a TOML file, e.g.
+//
+//     [simulation]
+//     trials = 10000000
+//     seed = 7
+//
+//     [rules]
+//     faces = "1,2,3,4,5,8"
+//
+//     [strategies]
+//     select = ["Reroll if losing", "Greedy best power"]
+//
+// Any key left out keeps its default, and command-line flags win over the
+// file. An empty `select` list runs every strategy, and a missing bet bound
+// extends to the end of the game's bet range. `scenarios/` has complete
+// examples.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub simulation: SimulationConfig,
+    pub rules: RulesConfig,
+    pub strategies: StrategiesConfig,
+    pub output: OutputConfig,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationConfig {
+    pub trials: u64,
+    pub seed: Option<u64>,
+    pub power_availability: f64,
+    pub bet_min: Option<u16>,
+    pub bet_max: Option<u16>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            trials: 1_000_000,
+            seed: None,
+            power_availability: 1.0,
+            bet_min: None,
+            bet_max: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RulesConfig {
+    pub consolation: u8,
+    pub payout: String,
+    pub faces: String,
+    pub roll: String,
+    pub exploding: bool,
+    pub max_bet: Option<u16>,
+    pub reroll_cost: i64,
+    pub flip_cost: i64,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        let rules = GameRules::default();
+        let costs = PowerCosts::default();
+        Self {
+            consolation: rules.consolation,
+            payout: rules.payout.to_string(),
+            faces: rules.faces.to_string(),
+            roll: rules.roll.to_string(),
+            exploding: false,
+            max_bet: None,
+            reroll_cost: costs.reroll,
+            flip_cost: costs.flip,
+        }
+    }
+}
+
+// Strategies are picked by name. The parameterized ones only run when their
+// parameter is given.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StrategiesConfig {
+    pub select: Vec<String>,
+    pub reroll_budget: Option<u8>,
+    pub tree: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    pub format: String,
+    // Where results go instead of stdout.
+    pub path: Option<PathBuf>,
+    pub dump_trials: Option<PathBuf>,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::Table.to_string(),
+            path: None,
+            dump_trials: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn from_toml(toml: &str) -> Result<Self, SimulationError> {
+        let config: Config =
+            toml::from_str(toml).map_err(|err| SimulationError::InvalidConfig(err.to_string()))?;
+        config.payout()?;
+        config.faces()?;
+        config.roll()?;
+        config.output_format()?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, SimulationError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    // Every key with its default value, as a starting point for a file.
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).expect("configs always serialize")
+    }
+
+    pub fn payout(&self) -> Result<PayoutRule, SimulationError> {
+        key("rules.payout", self.rules.payout.parse())
+    }
+
+    pub fn faces(&self) -> Result<Faces, SimulationError> {
+        key("rules.faces", self.rules.faces.parse())
+    }
+
+    pub fn roll(&self) -> Result<RollMode, SimulationError> {
+        key("rules.roll", self.rules.roll.parse())
+    }
+
+    pub fn output_format(&self) -> Result<OutputFormat, SimulationError> {
+        key("output.format", self.output.format.parse())
+    }
+
+    pub fn bets(&self, rules: &GameRules) -> Result<(Bet, Bet), SimulationError> {
+        let range = rules.bet_range();
+        let (min, max) = (
+            Bet::new(self.simulation.bet_min.unwrap_or(*range.start()), rules)?,
+            Bet::new(self.simulation.bet_max.unwrap_or(*range.end()), rules)?,
+        );
+        if min > max {
+            return Err(SimulationError::InvalidConfig(format!(
+                "simulation.bet_min {} is greater than simulation.bet_max {}",
+                min, max
+            )));
+        }
+        Ok((min, max))
+    }
+
+    // Whether a strategy was selected, by case-insensitive name.
+    pub fn selects(&self, name: &str) -> bool {
+        self.strategies.select.is_empty()
+            || self
+                .strategies
+                .select
+                .iter()
+                .any(|selected| selected.eq_ignore_ascii_case(name))
+    }
+
+    pub fn restrict(
+        &self,
+        outcome: &Outcome,
+        rules: &GameRules,
+    ) -> Result<Outcome, SimulationError> {
+        let (min, max) = self.bets(rules)?;
+        Ok(outcome.filter(|bet| min <= bet && bet <= max))
+    }
+}
+
+// Names the offending key in a value's parse error.
+fn key<T>(name: &str, parsed: Result<T, SimulationError>) -> Result<T, SimulationError> {
+    parsed.map_err(|err| SimulationError::InvalidConfig(format!("{}: {}", name, err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_default_config_round_trips() {
+        let toml = Config::default().to_toml();
+        let config = Config::from_toml(&toml).unwrap();
+        assert_eq!(config.to_toml(), toml);
+        assert_eq!(config.simulation.trials, 1_000_000);
+        assert_eq!(config.payout().unwrap(), GameRules::default().payout);
+        assert_eq!(config.faces().unwrap(), Faces::default());
+        assert_eq!(config.output_format().unwrap(), OutputFormat::Table);
+        assert!(config.selects("Reroll if losing"));
+    }
+
+    #[test]
+    fn the_example_scenario_parses() {
+        let config = Config::from_toml(include_str!("../scenarios/expensive-powers.toml")).unwrap();
+        assert_eq!(config.simulation.trials, 2_000_000);
+        assert_eq!(config.simulation.seed, Some(1));
+        assert_eq!(config.simulation.power_availability, 0.5);
+        assert_eq!(
+            (config.simulation.bet_min, config.simulation.bet_max),
+            (Some(6), None)
+        );
+        assert_eq!(config.rules.consolation, 1);
+        assert_eq!(config.faces().unwrap(), "2,3,4,5,6,6".parse().unwrap());
+        assert_eq!((config.rules.reroll_cost, config.rules.flip_cost), (1, 2));
+        assert_eq!(config.payout().unwrap(), GameRules::default().payout);
+        assert_eq!(
+            config.strategies.select,
+            ["No change", "Reroll if losing", "Greedy best power"]
+        );
+        assert!(config.selects("greedy best power") && !config.selects("Always flip"));
+        let rules = GameRules {
+            faces: config.faces().unwrap(),
+            ..GameRules::default()
+        };
+        let (min, max) = config.bets(&rules).unwrap();
+        assert_eq!((min.value(), max.value()), (6, 12));
+    }
+
+    #[test]
+    fn errors_name_the_key() {
+        for (toml, key) in [
+            ("[rules]\nfaces = \"0,1\"", "rules.faces"),
+            ("[rules]\npayout = \"double\"", "rules.payout"),
+            ("[simulation]\ntrails = 5", "trails"),
+        ] {
+            match Config::from_toml(toml) {
+                Err(SimulationError::InvalidConfig(message)) => {
+                    assert!(message.contains(key), "{}", message)
+                }
+                other => panic!("{:?}", other),
+            }
+        }
+    }
+}
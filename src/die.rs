@@ -0,0 +1,324 @@
+use std::fmt;
+use std::iter;
+use std::num::NonZeroU8;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::SimulationError;
+
+// A single die showing one of its faces, which are numbered from 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Die(u8);
+
+impl Die {
+    pub fn new(face: u8, sides: u8) -> Result<Self, SimulationError> {
+        if (1..=sides).contains(&face) {
+            Ok(Die(face))
+        } else {
+            Err(SimulationError::InvalidDieFace { face, sides })
+        }
+    }
+
+    // One of `faces`, which needn't be numbered from 1.
+    pub fn on(face: u8, faces: Faces) -> Result<Self, SimulationError> {
+        if faces.contains(face) {
+            Ok(Die(face))
+        } else {
+            Err(SimulationError::InvalidFaces(format!(
+                "{} isn't one of {}",
+                face, faces
+            )))
+        }
+    }
+
+    // For faces already known to be on the die.
+    pub(crate) fn unchecked(face: u8) -> Self {
+        Die(face)
+    }
+
+    // A die needs at least one side to land on.
+    pub fn roll<R: Rng + ?Sized>(sides: NonZeroU8, rng: &mut R) -> Self {
+        Die(rng.gen_range(1, u16::from(sides.get()) + 1) as u8)
+    }
+
+    pub fn face(&self) -> u8 {
+        self.0
+    }
+
+    // Turns the die to show the face closest to `target` from above, or its
+    // highest face if they're all below it.
+    pub fn flip_to(self, target: u8, faces: Faces) -> Self {
+        Die(faces.at_least(target))
+    }
+
+    // The next face up, staying on the highest face.
+    pub fn increment(self, faces: Faces) -> Self {
+        Die(faces
+            .values()
+            .iter()
+            .copied()
+            .find(|&face| face > self.0)
+            .unwrap_or(self.0))
+    }
+}
+
+impl From<Die> for u16 {
+    fn from(die: Die) -> u16 {
+        die.0.into()
+    }
+}
+
+impl fmt::Display for Die {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+pub const MAX_SIDES: usize = 20;
+
+// The most a die's weights can add up to. Exact fractions are over the product
+// of the totals for every die rolled, so this keeps their denominators small.
+pub const MAX_TOTAL_WEIGHT: u32 = 100;
+
+// The values printed on a die's faces, in increasing order, from one face up
+// to `MAX_SIDES`. They needn't be distinct or contiguous, but none can be 0.
+// Each face comes up in proportion to its weight, which is 1 for a fair die.
+// Kept in fixed arrays so dice stay `Copy`; the unused tails are always 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Faces {
+    values: [u8; MAX_SIDES],
+    weights: [u8; MAX_SIDES],
+    sides: u8,
+    fair: bool,
+}
+
+impl Faces {
+    pub fn new(faces: &[u8]) -> Result<Self, SimulationError> {
+        let weighted: Vec<(u8, u8)> = faces.iter().map(|&face| (face, 1)).collect();
+        Self::weighted(&weighted)
+    }
+
+    // Faces paired with their weights. A weight of 0 keeps a face on the die,
+    // e.g. to flip to, without it ever being rolled.
+    pub fn weighted(faces: &[(u8, u8)]) -> Result<Self, SimulationError> {
+        if faces.is_empty() || faces.len() > MAX_SIDES {
+            return Err(SimulationError::InvalidFaces(format!(
+                "{} faces, expected 1 to {}",
+                faces.len(),
+                MAX_SIDES
+            )));
+        }
+        let mut sorted = faces.to_vec();
+        sorted.sort_unstable();
+        if sorted[0].0 == 0 {
+            return Err(SimulationError::InvalidFaces("no face can be 0".to_owned()));
+        }
+        let total: u32 = sorted.iter().map(|&(_, weight)| u32::from(weight)).sum();
+        if total == 0 || total > MAX_TOTAL_WEIGHT {
+            return Err(SimulationError::InvalidFaces(format!(
+                "weights add up to {}, expected 1 to {}",
+                total, MAX_TOTAL_WEIGHT
+            )));
+        }
+        let (mut values, mut weights) = ([0; MAX_SIDES], [0; MAX_SIDES]);
+        for (i, &(value, weight)) in sorted.iter().enumerate() {
+            values[i] = value;
+            weights[i] = weight;
+        }
+        Ok(Faces {
+            values,
+            weights,
+            sides: faces.len() as u8,
+            fair: sorted.iter().all(|&(_, weight)| weight == sorted[0].1),
+        })
+    }
+
+    pub fn values(&self) -> &[u8] {
+        &self.values[..self.sides()]
+    }
+
+    pub fn weights(&self) -> &[u8] {
+        &self.weights[..self.sides()]
+    }
+
+    pub fn total_weight(&self) -> u32 {
+        self.weights().iter().map(|&weight| u32::from(weight)).sum()
+    }
+
+    // Each distinct face that can come up, with the weight of every face
+    // showing it, in increasing order of face.
+    pub fn counts(&self) -> impl Iterator<Item = (u8, u32)> + '_ {
+        let mut faces = self.values().iter().zip(self.weights()).peekable();
+        iter::from_fn(move || {
+            let (&value, &weight) = faces.next()?;
+            let mut weight = u32::from(weight);
+            while let Some((_, &more)) = faces.next_if(|&(&next, _)| next == value) {
+                weight += u32::from(more);
+            }
+            Some((value, weight))
+        })
+        .filter(|&(_, weight)| weight > 0)
+    }
+
+    // Each distinct face that can come up, with its probability.
+    pub fn outcomes(&self) -> impl Iterator<Item = (u8, f64)> + '_ {
+        let total = f64::from(self.total_weight());
+        self.counts()
+            .map(move |(value, weight)| (value, f64::from(weight) / total))
+    }
+
+    pub fn sides(&self) -> usize {
+        self.sides.into()
+    }
+
+    pub fn min(&self) -> u8 {
+        self.values[0]
+    }
+
+    pub fn max(&self) -> u8 {
+        self.values[self.sides() - 1]
+    }
+
+    pub fn contains(&self, face: u8) -> bool {
+        self.values().contains(&face)
+    }
+
+    // Draws the same way as a six-sided `Die::roll` when every face is equally
+    // likely, so the standard faces roll exactly as a plain d6 would.
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> Die {
+        if self.fair {
+            return Die(self.values[rng.gen_range(0u8, self.sides) as usize]);
+        }
+        let mut draw = rng.gen_range(0, self.total_weight());
+        for (&value, &weight) in self.values().iter().zip(self.weights()) {
+            if draw < u32::from(weight) {
+                return Die(value);
+            }
+            draw -= u32::from(weight);
+        }
+        unreachable!("the draw is below the total weight")
+    }
+
+    // The face closest to `target` from above, or the highest face if they're
+    // all below it.
+    pub fn at_least(&self, target: u8) -> u8 {
+        self.values()
+            .iter()
+            .copied()
+            .find(|&face| face >= target)
+            .unwrap_or_else(|| self.max())
+    }
+}
+
+impl Default for Faces {
+    fn default() -> Self {
+        let (mut values, mut weights) = ([0; MAX_SIDES], [0; MAX_SIDES]);
+        values[..6].copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+        weights[..6].copy_from_slice(&[1; 6]);
+        Faces {
+            values,
+            weights,
+            sides: 6,
+            fair: true,
+        }
+    }
+}
+
+// Like `1,2,3,4,5,6*3`, with weights after the faces that don't have 1.
+impl fmt::Display for Faces {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let faces: Vec<String> = self
+            .values()
+            .iter()
+            .zip(self.weights())
+            .map(|(value, weight)| match weight {
+                1 => value.to_string(),
+                _ => format!("{}*{}", value, weight),
+            })
+            .collect();
+        f.write_str(&faces.join(","))
+    }
+}
+
+// Comma-separated faces, like `1,1,2,3,5,8`, each optionally followed by `*`
+// and its weight, like `1,2,3,4,5,6*3` for a die loaded towards 6.
+impl FromStr for Faces {
+    type Err = SimulationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let faces = s
+            .split(',')
+            .map(|face| match face.split_once('*') {
+                Some((value, weight)) => Ok((value.trim().parse()?, weight.trim().parse()?)),
+                None => Ok((face.trim().parse()?, 1)),
+            })
+            .collect::<Result<Vec<(u8, u8)>, std::num::ParseIntError>>()
+            .map_err(|err| SimulationError::InvalidFaces(format!("{:?}: {}", s, err)))?;
+        Self::weighted(&faces)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{Dice, Power};
+
+    #[test]
+    fn flip_to_lands_on_a_face() {
+        let faces: Faces = "1,1,2,3,5,8".parse().unwrap();
+        assert_eq!(Die(1).flip_to(4, faces), Die(5));
+        assert_eq!(Die(1).flip_to(200, faces), Die(8));
+        assert_eq!(Die(1).flip_to(0, faces), Die(1));
+    }
+
+    #[test]
+    fn increment_stays_on_the_die() {
+        let faces: Faces = "1,1,2,3,5,8".parse().unwrap();
+        assert_eq!(Die(3).increment(faces), Die(5));
+        assert_eq!(Die(8).increment(faces), Die(8));
+        let high: Faces = "254,255".parse().unwrap();
+        assert_eq!(Die(255).increment(high), Die(255));
+    }
+
+    #[test]
+    fn fibonacci_dice_roll_only_their_faces() {
+        let faces: Faces = "1,1,2,3,5,8".parse().unwrap();
+        let mut rng = SmallRng::seed_from_u64(4);
+        let rolls: Vec<u8> = (0..6000).map(|_| faces.roll(&mut rng).face()).collect();
+        assert!(rolls.iter().all(|&face| faces.contains(face)));
+        let ones = rolls.iter().filter(|&&face| face == 1).count();
+        assert!((1800..2200).contains(&ones), "{} ones", ones);
+    }
+
+    #[test]
+    fn fibonacci_dice_sum_and_flip() {
+        let faces: Faces = "1,1,2,3,5,8".parse().unwrap();
+        assert_eq!(Dice::try_new(8, 5, faces).unwrap().sum(), 13);
+        assert!(Dice::try_new(4, 5, faces).is_err());
+        let flipped = Dice::try_new(1, 2, faces).unwrap().modify(Power::FLIP);
+        assert_eq!((flipped.d1.face(), flipped.d2.face()), (2, 5));
+        // The two 1s make one face with twice the weight.
+        let outcomes: Vec<(Dice, f64)> = Dice::outcomes(faces).collect();
+        assert_eq!(outcomes.len(), 25);
+        let mean: f64 = outcomes
+            .iter()
+            .map(|&(dice, p)| p * f64::from(dice.sum()))
+            .sum();
+        assert!((mean - 240.0 / 36.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn standard_faces_roll_like_a_plain_die() {
+        let (mut a, mut b) = (SmallRng::seed_from_u64(9), SmallRng::seed_from_u64(9));
+        for _ in 0..100 {
+            let six = NonZeroU8::new(6).unwrap();
+            assert_eq!(Faces::default().roll(&mut a), Die::roll(six, &mut b));
+        }
+        let one = NonZeroU8::new(1).unwrap();
+        assert_eq!(Die::roll(one, &mut a), Die(1));
+    }
+}
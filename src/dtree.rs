@@ -0,0 +1,162 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{Bet, Dice, Faces, Power, SimulationError, Strategy};
+
+pub type Condition = Box<dyn Fn(Bet, Dice) -> bool>;
+
+// A strategy spelled out as a tree of yes/no questions about the bet and the
+// roll, with the power to use at each leaf.
+pub enum DTree {
+    Leaf(Power),
+    Branch {
+        condition: Condition,
+        if_true: Box<DTree>,
+        if_false: Box<DTree>,
+    },
+}
+
+impl DTree {
+    pub fn branch(
+        condition: impl Fn(Bet, Dice) -> bool + 'static,
+        if_true: DTree,
+        if_false: DTree,
+    ) -> Self {
+        DTree::Branch {
+            condition: Box::new(condition),
+            if_true: Box::new(if_true),
+            if_false: Box::new(if_false),
+        }
+    }
+
+    // `RerollIfLosingOrFlip` as a tree.
+    pub fn reroll_if_losing_or_flip() -> Self {
+        DTree::branch(
+            |bet, dice| bet <= dice.sum(),
+            DTree::Leaf(Power::None),
+            DTree::branch(
+                |bet, dice| bet <= dice.modify(Power::FLIP).sum(),
+                DTree::Leaf(Power::FLIP),
+                DTree::Leaf(Power::Reroll(Dice::unchecked(1, 1))),
+            ),
+        )
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SimulationError> {
+        let spec: TreeSpec = serde_json::from_str(json)
+            .map_err(|err| SimulationError::InvalidTree(err.to_string()))?;
+        spec.build()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SimulationError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    // Checks every leaf's power against the dice it will be played with.
+    pub fn check(&self, faces: Faces) -> Result<(), SimulationError> {
+        match self {
+            DTree::Leaf(power) => power.check(faces).map(|_| ()),
+            DTree::Branch {
+                if_true, if_false, ..
+            } => {
+                if_true.check(faces)?;
+                if_false.check(faces)
+            }
+        }
+    }
+}
+
+impl Strategy for DTree {
+    fn name(&self) -> &str {
+        "Decision tree"
+    }
+
+    fn id(&self) -> &str {
+        "DTree"
+    }
+
+    fn description(&self) -> &'static str {
+        "Follows the decision tree loaded with --tree."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        match self {
+            DTree::Leaf(power) => *power,
+            DTree::Branch {
+                condition,
+                if_true,
+                if_false,
+            } => {
+                if condition(bet, dice) {
+                    if_true.choose_power(bet, dice)
+                } else {
+                    if_false.choose_power(bet, dice)
+                }
+            }
+        }
+    }
+}
+
+// The serialized form of a tree. Closures can't be read from a file, so
+// branches pick from a fixed set of named conditions, e.g.
+//
+//     {"condition": "winning",
+//      "if_true": {"power": "none"},
+//      "if_false": {"condition": {"sum_at_least": 5},
+//                   "if_true": {"power": "flip"},
+//                   "if_false": {"power": "reroll"}}}
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TreeSpec {
+    Leaf {
+        power: String,
+    },
+    Branch {
+        condition: ConditionSpec,
+        if_true: Box<TreeSpec>,
+        if_false: Box<TreeSpec>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ConditionSpec {
+    // The roll already meets the bet.
+    Winning,
+    // Flipping one die would meet the bet.
+    FlipWins,
+    SumAtLeast(u16),
+    BetAtLeast(u16),
+    LowDieAtMost(u8),
+}
+
+impl ConditionSpec {
+    fn test(self, bet: Bet, dice: Dice) -> bool {
+        match self {
+            ConditionSpec::Winning => bet <= dice.sum(),
+            ConditionSpec::FlipWins => bet <= dice.modify(Power::FLIP).sum(),
+            ConditionSpec::SumAtLeast(sum) => sum <= dice.sum(),
+            ConditionSpec::BetAtLeast(min) => min <= bet,
+            ConditionSpec::LowDieAtMost(face) => dice.d1.face() <= face,
+        }
+    }
+}
+
+impl TreeSpec {
+    fn build(self) -> Result<DTree, SimulationError> {
+        Ok(match self {
+            TreeSpec::Leaf { power } => DTree::Leaf(power.parse()?),
+            TreeSpec::Branch {
+                condition,
+                if_true,
+                if_false,
+            } => DTree::branch(
+                move |bet, dice| condition.test(bet, dice),
+                if_true.build()?,
+                if_false.build()?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+use rand::RngCore;
+use serde::Serialize;
+
+use crate::{ApplicablePower, Bet, GameRules, Gold, PowerCosts, Round, SimulationError, Strategy};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DumpFormat {
+    Csv,
+    // One JSON object per line.
+    Json,
+}
+
+impl DumpFormat {
+    // `.csv`, or `.jsonl`, `.ndjson`, or `.json`.
+    pub fn from_path(path: &Path) -> Result<Self, SimulationError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("csv") => Ok(DumpFormat::Csv),
+            Some("jsonl") | Some("ndjson") | Some("json") => Ok(DumpFormat::Json),
+            _ => Err(SimulationError::UnknownDumpFormat(
+                path.display().to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TrialRecord<'a> {
+    strategy: &'a str,
+    bet: Bet,
+    initial_sum: u16,
+    power: String,
+    final_sum: u16,
+    gold: Gold,
+    cost: Gold,
+}
+
+// Streams one record per round to `out`, which should be buffered.
+pub struct TrialWriter<W: Write> {
+    out: W,
+    format: DumpFormat,
+}
+
+impl<W: Write> TrialWriter<W> {
+    // Starts the dump with the run's parameters: as `#` comments and a column
+    // header for CSV, or as a leading `{"parameters": ...}` line for JSON.
+    pub fn new(
+        mut out: W,
+        format: DumpFormat,
+        parameters: &[(&str, String)],
+    ) -> Result<Self, SimulationError> {
+        match format {
+            DumpFormat::Csv => {
+                for (name, value) in parameters {
+                    writeln!(out, "# {} = {}", name, value)?;
+                }
+                writeln!(out, "strategy,bet,initial_sum,power,final_sum,gold,cost")?;
+            }
+            DumpFormat::Json => {
+                let parameters: serde_json::Map<String, serde_json::Value> = parameters
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.clone().into()))
+                    .collect();
+                serde_json::to_writer(&mut out, &serde_json::json!({ "parameters": parameters }))
+                    .map_err(std::io::Error::from)?;
+                writeln!(out)?;
+            }
+        }
+        Ok(Self { out, format })
+    }
+
+    pub fn write<P: fmt::Display>(
+        &mut self,
+        strategy: &str,
+        round: &Round<P>,
+    ) -> Result<(), SimulationError> {
+        match self.format {
+            DumpFormat::Csv => writeln!(
+                self.out,
+                "\"{}\",{},{},{},{},{},{}",
+                strategy.replace('"', "\"\""),
+                round.bet,
+                round.rolled.sum(),
+                round.power,
+                round.dice.sum(),
+                round.gold,
+                round.cost
+            )?,
+            DumpFormat::Json => {
+                let record = TrialRecord {
+                    strategy,
+                    bet: round.bet,
+                    initial_sum: round.rolled.sum(),
+                    power: round.power.to_string(),
+                    final_sum: round.dice.sum(),
+                    gold: round.gold,
+                    cost: round.cost,
+                };
+                serde_json::to_writer(&mut self.out, &record).map_err(std::io::Error::from)?;
+                writeln!(self.out)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Plays `trials` rounds of `strategy` at each of `bets`, writing each one
+    // as it's played.
+    #[allow(clippy::too_many_arguments)]
+    pub fn dump<P: ApplicablePower + fmt::Display, S: Strategy<P> + ?Sized>(
+        &mut self,
+        strategy: &S,
+        bets: impl Iterator<Item = Bet>,
+        trials: u64,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Result<(), SimulationError> {
+        for bet in bets {
+            for _ in 0..trials {
+                let round = strategy.round(bet, rules, power_availability, costs, rng);
+                self.write(strategy.name(), &round)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<(), SimulationError> {
+        self.out.flush()?;
+        Ok(())
+    }
+}
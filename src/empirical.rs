@@ -0,0 +1,151 @@
+use std::path::Path;
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::search::{rolls, PolicyTable};
+use crate::{
+    ApplicablePower, Bet, Dice, GameRules, Gold, Power, PowerCosts, SimulationError, Strategy,
+};
+
+// A policy learned from play instead of worked out: for every bet and roll,
+// each power is tried `trials_per_state` times and the one with the most net
+// gold on average is kept. Saved tables are ordinary policy tables, so they
+// also load with `--policy`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EmpiricalStrategy(pub PolicyTable);
+
+impl EmpiricalStrategy {
+    // Every power sees the same rerolls at each roll, so powers that are
+    // really tied, like rerolling with or without keeping a roll that loses
+    // anyway, tie exactly. Ties keep the earlier of doing nothing, rerolling,
+    // rerolling and keeping the better roll, and flipping, as `MarkovOptimal`
+    // does, so with enough trials the two agree.
+    pub fn train<R: Rng + ?Sized>(
+        trials_per_state: u64,
+        rules: &GameRules,
+        costs: &PowerCosts,
+        rng: &mut R,
+    ) -> Result<Self, SimulationError> {
+        if trials_per_state == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        let powers = [
+            Power::None,
+            Power::Reroll(Dice::unchecked(1, 1)),
+            Power::RerollKeepBest(Dice::unchecked(1, 1)),
+            Power::FLIP,
+        ];
+        let mut table = PolicyTable::empty(rules);
+        for bet in rules.bets() {
+            for dice in rolls(rules.faces) {
+                let seed = rng.gen();
+                let mut most = f64::NEG_INFINITY;
+                for &power in &powers {
+                    let mut rng = SmallRng::seed_from_u64(seed);
+                    let net: Gold = (0..trials_per_state)
+                        .map(|_| {
+                            let drawn = power.draw(rules, &mut rng);
+                            drawn.apply(dice).gold(bet, rules) - drawn.cost(costs)
+                        })
+                        .sum();
+                    let average = f64::from(net) / trials_per_state as f64;
+                    if average > most {
+                        most = average;
+                        table
+                            .powers
+                            .insert((bet.value(), dice.d1.face(), dice.d2.face()), power);
+                    }
+                }
+            }
+        }
+        Ok(Self(table))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SimulationError> {
+        PolicyTable::from_json(json).map(Self)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SimulationError> {
+        PolicyTable::load(path).map(Self)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SimulationError> {
+        self.0.save(path)
+    }
+}
+
+impl Strategy for EmpiricalStrategy {
+    fn name(&self) -> &str {
+        "Empirical lookup table"
+    }
+
+    fn id(&self) -> &str {
+        "EmpiricalStrategy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Uses the power that earned the most for each bet and roll in training with --train."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        self.0.choose_power(bet, dice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+
+    use super::*;
+    use crate::markov::MarkovOptimal;
+
+    fn trained() -> EmpiricalStrategy {
+        let mut rng = SmallRng::seed_from_u64(11);
+        EmpiricalStrategy::train(
+            2_000,
+            &GameRules::default(),
+            &PowerCosts::default(),
+            &mut rng,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn training_finds_the_optimal_powers() {
+        let (rules, costs) = (GameRules::default(), PowerCosts::default());
+        let (empirical, optimal) = (trained(), MarkovOptimal(costs));
+        for (bet, d1, d2) in [
+            (7, 6, 6),
+            (7, 1, 1),
+            (7, 3, 3),
+            (10, 4, 6),
+            (12, 2, 6),
+            (4, 1, 2),
+        ] {
+            let (bet, dice) = (Bet::new(bet, &rules).unwrap(), Dice::new(d1, d2).unwrap());
+            assert_eq!(
+                empirical.choose_power(bet, dice),
+                optimal.choose_power(bet, dice),
+                "{:?} at {}",
+                dice,
+                bet
+            );
+        }
+        let (learned, best) = (
+            empirical.exact_outcome(&rules, 1.0, &costs).unwrap(),
+            optimal.exact_outcome(&rules, 1.0, &costs).unwrap(),
+        );
+        for (bet, ev) in learned.iter() {
+            assert!(best[bet.value()] - ev < 0.01, "{} at {}", ev, bet);
+        }
+    }
+
+    #[test]
+    fn tables_round_trip_through_json() {
+        let empirical = trained();
+        let json = serde_json::to_string(&empirical).unwrap();
+        assert_eq!(EmpiricalStrategy::from_json(&json).unwrap(), empirical);
+    }
+}
@@ -0,0 +1,124 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum SimulationError {
+    InvalidBet { bet: u16, min: u16, max: u16 },
+    InvalidTrialCount,
+    InvalidRoundCount,
+    InvalidBankroll(i64),
+    InvalidDieFace { face: u8, sides: u8 },
+    UnknownPower(String),
+    InvalidProbability(f64),
+    InvalidPrecision(f64),
+    InvalidTree(String),
+    UnknownPayoutRule(String),
+    UnknownRollMode(String),
+    InvalidConfig(String),
+    InvalidFaces(String),
+    UnknownDumpFormat(String),
+    InvalidWeights(String),
+    UnknownOutputFormat(String),
+    MismatchedBets,
+    InvalidCheckpoint(String),
+    InvalidRecording(String),
+    InvalidPolicy(String),
+    InvalidPlayers(String),
+    UnknownPlotFormat(String),
+    Plot(String),
+    UnknownStrategy(String),
+    NotEnumerable(String),
+    GoldOutOfRange { what: &'static str, gold: i64 },
+    GoldOverflow { left: i64, op: char, right: i64 },
+    IoError(io::Error),
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBet { bet, min, max } => {
+                write!(f, "bet {} is outside of {}..={}", bet, min, max)
+            }
+            Self::InvalidTrialCount => write!(f, "at least one trial is required"),
+            Self::InvalidRoundCount => write!(f, "a session needs at least one round"),
+            Self::InvalidBankroll(bankroll) => {
+                write!(f, "a session needs a positive bankroll, not {}", bankroll)
+            }
+            Self::InvalidDieFace { face, sides } => {
+                write!(f, "{} is not a face of a {}-sided die", face, sides)
+            }
+            Self::UnknownPower(power) => write!(
+                f,
+                "unknown power {:?}, expected one of none, reroll, reroll-best, flip, flip:<target>",
+                power
+            ),
+            Self::InvalidProbability(p) => {
+                write!(f, "probability {} is outside of 0..=1", p)
+            }
+            Self::InvalidPrecision(epsilon) => {
+                write!(f, "precision {} must be greater than 0", epsilon)
+            }
+            Self::InvalidTree(err) => write!(f, "invalid decision tree: {}", err),
+            Self::UnknownPayoutRule(rule) => {
+                write!(f, "unknown payout rule {:?}, expected bet or sum", rule)
+            }
+            Self::UnknownRollMode(mode) => write!(
+                f,
+                "unknown roll mode {:?}, expected normal, advantage, or disadvantage",
+                mode
+            ),
+            Self::InvalidConfig(err) => write!(f, "invalid config: {}", err),
+            Self::InvalidFaces(err) => write!(f, "invalid die faces: {}", err),
+            Self::UnknownDumpFormat(path) => write!(
+                f,
+                "can't tell the dump format of {:?}, expected a .csv or .jsonl file",
+                path
+            ),
+            Self::InvalidWeights(err) => write!(f, "invalid weights: {}", err),
+            Self::UnknownOutputFormat(format) => {
+                write!(f, "unknown output format {:?}, expected table, chart, or latex", format)
+            }
+            Self::MismatchedBets => write!(f, "results cover different bets"),
+            Self::InvalidCheckpoint(err) => write!(f, "invalid checkpoint: {}", err),
+            Self::InvalidRecording(err) => write!(f, "invalid recording: {}", err),
+            Self::InvalidPolicy(err) => write!(f, "invalid policy table: {}", err),
+            Self::InvalidPlayers(err) => write!(f, "invalid players: {}", err),
+            Self::UnknownPlotFormat(path) => write!(
+                f,
+                "can't tell the plot format of {:?}, expected a .svg or .png file",
+                path
+            ),
+            Self::Plot(err) => write!(f, "couldn't draw the plot: {}", err),
+            Self::UnknownStrategy(name) => write!(f, "unknown strategy {:?}", name),
+            Self::NotEnumerable(err) => write!(f, "{}", err),
+            Self::GoldOutOfRange { what, gold } => write!(
+                f,
+                "{} {} is outside of -{}..={}",
+                what,
+                gold,
+                crate::MAX_GOLD,
+                crate::MAX_GOLD
+            ),
+            Self::GoldOverflow { left, op, right } => {
+                write!(f, "{} {} {} gold overflows", left, op, right)
+            }
+            Self::IoError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for SimulationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IoError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SimulationError {
+    fn from(err: io::Error) -> Self {
+        Self::IoError(err)
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+
+use crate::{Bet, Dice, GameRules, Power, PowerCosts, SimulationError, Strategy};
+
+// A power for every bet and sum of the dice. Pairs missing from the table do
+// nothing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LookupStrategy {
+    pub table: HashMap<(u16, u16), Power>,
+}
+
+impl Strategy for LookupStrategy {
+    fn name(&self) -> &str {
+        "Evolved lookup table"
+    }
+
+    fn id(&self) -> &str {
+        "LookupStrategy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Uses a power per bet and sum, found by a genetic search with --evolve."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        self.table
+            .get(&(bet.value(), dice.sum()))
+            .copied()
+            .unwrap_or(Power::None)
+    }
+}
+
+// Searches for a good table by evolution: start from `population` random
+// tables, then each generation keep the better half and replace the rest with
+// copies of the survivors, each with one cell changed to a different power.
+// A table's fitness is its total expected gold over every bet, sampled with
+// `trials_per_eval` trials per bet.
+#[allow(clippy::too_many_arguments)]
+pub fn evolve_strategy<R: Rng + ?Sized>(
+    rng: &mut R,
+    generations: u32,
+    population: u32,
+    trials_per_eval: u64,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+) -> Result<LookupStrategy, SimulationError> {
+    let powers = [
+        Power::None,
+        Power::Reroll(Dice::unchecked(1, 1)),
+        Power::RerollKeepBest(Dice::unchecked(1, 1)),
+        Power::FLIP,
+    ];
+    let mut sums: Vec<u16> = rules
+        .roll
+        .outcomes(rules.faces)
+        .into_iter()
+        .map(|(dice, _)| dice.sum())
+        .collect();
+    sums.sort_unstable();
+    sums.dedup();
+    let cells: Vec<(u16, u16)> = rules
+        .bets()
+        .flat_map(|bet| sums.iter().map(move |&sum| (bet.value(), sum)))
+        .collect();
+    // Scoring draws from its own stream, so it doesn't need to borrow `rng`.
+    let mut sampler = SmallRng::seed_from_u64(rng.gen());
+    let mut fitness = |strategy: &LookupStrategy| -> Result<f64, SimulationError> {
+        Ok(strategy
+            .avg_outcome(
+                trials_per_eval,
+                rules,
+                power_availability,
+                costs,
+                &mut sampler,
+            )?
+            .ev
+            .iter()
+            .sum())
+    };
+
+    let mut scored = (0..population.max(2))
+        .map(|_| {
+            let strategy = LookupStrategy {
+                table: cells
+                    .iter()
+                    .map(|&cell| (cell, *powers.choose(rng).expect("there are powers")))
+                    .collect(),
+            };
+            Ok((fitness(&strategy)?, strategy))
+        })
+        .collect::<Result<Vec<_>, SimulationError>>()?;
+    for _ in 0..generations {
+        scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        let survivors = scored.len().div_ceil(2);
+        for i in survivors..scored.len() {
+            let mut child = scored[i % survivors].1.clone();
+            let cell = cells.choose(rng).expect("there are bets");
+            let current = child.table[cell];
+            let power = *powers
+                .iter()
+                .filter(|&&power| power != current)
+                .collect::<Vec<_>>()
+                .choose(rng)
+                .expect("there are other powers");
+            child.table.insert(*cell, *power);
+            scored[i] = (fitness(&child)?, child);
+        }
+    }
+    Ok(scored
+        .into_iter()
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, strategy)| strategy)
+        .expect("the population is never empty"))
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    check_probability, ApplicablePower, Bet, Dice, GameRules, Power, PowerCosts, SimulationError,
+    Strategy, ToF64,
+};
+
+// An exact expected value, in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Fraction {
+    pub numerator: i64,
+    pub denominator: u64,
+}
+
+impl Fraction {
+    pub fn new(numerator: i64, denominator: u64) -> Self {
+        assert!(denominator > 0, "a fraction's denominator can't be 0");
+        let divisor = gcd(numerator.unsigned_abs(), denominator);
+        Self {
+            numerator: numerator / divisor as i64,
+            denominator: denominator / divisor,
+        }
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+impl ToF64 for Fraction {
+    fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+// Like `Strategy::exact_outcome`, but in integers: every roll and reroll is
+// weighted by its faces' weights, so each bet's expected net gold is the
+// weighted total over every roll and reroll divided by the total weight.
+// Availability has to be all or nothing for the answer to stay a fraction of
+// small integers.
+pub fn exact_fractions<S: Strategy + ?Sized>(
+    strategy: &S,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+) -> Result<Vec<(Bet, Fraction)>, SimulationError> {
+    check_probability(power_availability)?;
+    rules.check_enumerable()?;
+    if !strategy.is_deterministic() {
+        return Err(SimulationError::NotEnumerable(format!(
+            "{:?} chooses at random, so it has no exact solution",
+            strategy.name()
+        )));
+    }
+    if power_availability != 0.0 && power_availability != 1.0 {
+        return Err(SimulationError::NotEnumerable(
+            "exact fractions need powers to be always or never available".to_owned(),
+        ));
+    }
+    let rolls = rules.roll.counts(rules.faces);
+    let rerolls: Vec<(Dice, u64)> = Dice::counts(rules.faces).collect();
+    let total = |counts: &[(Dice, u64)]| counts.iter().map(|&(_, weight)| weight).sum::<u64>();
+    let reroll_total = total(&rerolls);
+    let denominator = total(&rolls) * reroll_total;
+    Ok(rules
+        .bets()
+        .map(|bet| {
+            let numerator: i64 = rolls
+                .iter()
+                .map(|&(dice, weight)| {
+                    let power = if power_availability == 1.0 {
+                        strategy.choose_power_within(bet, dice, &strategy.budget())
+                    } else {
+                        Power::None
+                    };
+                    let cost = power.cost(costs);
+                    let gold: i64 = match power {
+                        Power::Reroll(_) | Power::RerollKeepBest(_) => rerolls
+                            .iter()
+                            .map(|&(new, weight)| {
+                                let power = match power {
+                                    Power::Reroll(_) => Power::Reroll(new),
+                                    _ => Power::RerollKeepBest(new),
+                                };
+                                (dice.modify(power).gold(bet, rules) - cost).0 * weight as i64
+                            })
+                            .sum(),
+                        power => {
+                            (dice.modify(power).gold(bet, rules) - cost).0 * reroll_total as i64
+                        }
+                    };
+                    gold * weight as i64
+                })
+                .sum();
+            (bet, Fraction::new(numerator, denominator))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{strategies, NoPower};
+
+    #[test]
+    fn fractions_are_in_lowest_terms() {
+        assert_eq!(Fraction::new(177, 36), Fraction::new(59, 12));
+        assert_eq!(Fraction::new(-6, 4).to_string(), "-3/2");
+        assert_eq!(Fraction::new(0, 36).to_string(), "0");
+        assert_eq!(Fraction::new(72, 36).to_string(), "2");
+    }
+
+    #[test]
+    fn no_power_at_bet_7_is_59_twelfths() {
+        let (rules, costs) = (GameRules::default(), PowerCosts::default());
+        let fractions = exact_fractions(&NoPower(), &rules, 1.0, &costs).unwrap();
+        let (_, seven) = fractions.iter().find(|(bet, _)| *bet == 7u16).unwrap();
+        assert_eq!(*seven, Fraction::new(59, 12));
+    }
+
+    #[test]
+    fn fractions_match_the_floating_point_evs() {
+        let rules = GameRules::default();
+        for costs in [PowerCosts::default(), PowerCosts { reroll: 1, flip: 2 }] {
+            for strategy in strategies(&rules, &costs) {
+                for power_availability in [0.0, 1.0] {
+                    let fractions =
+                        exact_fractions(&*strategy, &rules, power_availability, &costs).unwrap();
+                    let outcome = strategy
+                        .exact_outcome(&rules, power_availability, &costs)
+                        .unwrap();
+                    for (bet, fraction) in fractions {
+                        assert!(
+                            (fraction.to_f64() - outcome[bet.value()]).abs() < 1e-9,
+                            "{} at {}: {} vs {}",
+                            strategy.name(),
+                            bet,
+                            fraction,
+                            outcome[bet.value()]
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn partial_availability_has_no_fraction() {
+        let (rules, costs) = (GameRules::default(), PowerCosts::default());
+        assert!(matches!(
+            exact_fractions(&NoPower(), &rules, 0.5, &costs),
+            Err(SimulationError::NotEnumerable(_))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Neg, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{SimulationError, ToF64};
+
+// An amount of gold: a payout, a power's cost, or a bankroll. Signed, since
+// costs and lost stakes can leave a player behind. A round's gold is far too
+// small to overflow, so the operators don't check; running totals, which can
+// grow without bound, use `checked_add` and `checked_sub`.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Gold(pub i64);
+
+impl Gold {
+    pub const ZERO: Gold = Gold(0);
+
+    pub fn checked_add(self, other: Gold) -> Result<Gold, SimulationError> {
+        self.0
+            .checked_add(other.0)
+            .map(Gold)
+            .ok_or(SimulationError::GoldOverflow {
+                left: self.0,
+                op: '+',
+                right: other.0,
+            })
+    }
+
+    pub fn checked_sub(self, other: Gold) -> Result<Gold, SimulationError> {
+        self.0
+            .checked_sub(other.0)
+            .map(Gold)
+            .ok_or(SimulationError::GoldOverflow {
+                left: self.0,
+                op: '-',
+                right: other.0,
+            })
+    }
+}
+
+impl From<u8> for Gold {
+    fn from(gold: u8) -> Self {
+        Gold(gold.into())
+    }
+}
+
+impl From<u16> for Gold {
+    fn from(gold: u16) -> Self {
+        Gold(gold.into())
+    }
+}
+
+impl From<Gold> for f64 {
+    fn from(gold: Gold) -> Self {
+        gold.0 as f64
+    }
+}
+
+impl ToF64 for Gold {
+    fn to_f64(self) -> f64 {
+        self.into()
+    }
+}
+
+impl Add for Gold {
+    type Output = Gold;
+
+    fn add(self, other: Gold) -> Gold {
+        Gold(self.0 + other.0)
+    }
+}
+
+impl Sub for Gold {
+    type Output = Gold;
+
+    fn sub(self, other: Gold) -> Gold {
+        Gold(self.0 - other.0)
+    }
+}
+
+impl Neg for Gold {
+    type Output = Gold;
+
+    fn neg(self) -> Gold {
+        Gold(-self.0)
+    }
+}
+
+impl Sum for Gold {
+    fn sum<I: Iterator<Item = Gold>>(iter: I) -> Gold {
+        iter.fold(Gold::ZERO, Add::add)
+    }
+}
+
+impl fmt::Display for Gold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bet, Dice, GameRules, PayoutRule};
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert_eq!(Gold(2).checked_add(Gold(3)).unwrap(), Gold(5));
+        assert_eq!(Gold(2).checked_sub(Gold(3)).unwrap(), Gold(-1));
+        let err = Gold(i64::MAX).checked_add(Gold(1)).unwrap_err();
+        assert_eq!(err.to_string(), format!("{} + 1 gold overflows", i64::MAX));
+        assert!(matches!(
+            Gold(i64::MIN).checked_sub(Gold(1)),
+            Err(SimulationError::GoldOverflow {
+                op: '-',
+                right: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn large_faces_pay_past_a_byte() {
+        let rules = GameRules {
+            payout: PayoutRule::Sum,
+            faces: "200,250".parse().unwrap(),
+            ..GameRules::default()
+        };
+        let bet = Bet::new(450, &rules).unwrap();
+        assert_eq!(Dice::unchecked(250, 250).gold(bet, &rules), Gold(500));
+        assert_eq!(Dice::unchecked(200, 250).gold(bet, &rules), Gold(450));
+        let bet = Bet::new(400, &rules).unwrap();
+        assert_eq!(Dice::unchecked(200, 200).gold(bet, &rules), Gold(400));
+    }
+}
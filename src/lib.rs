@@ -0,0 +1,2281 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter;
+use std::iter::FromIterator;
+use std::ops::Index;
+use std::str::FromStr;
+
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::stats::RunningStats;
+
+mod antithetic;
+mod bet;
+pub mod chart;
+pub mod checkpoint;
+pub mod compare;
+pub mod config;
+mod die;
+pub mod dtree;
+pub mod dump;
+pub mod empirical;
+pub mod error;
+pub mod evolve;
+pub mod exact;
+mod gold;
+pub mod markov;
+pub mod multiplayer;
+pub mod optimize;
+pub mod play;
+#[cfg(feature = "plot")]
+pub mod plot;
+pub mod policy;
+pub mod progress;
+pub mod provenance;
+pub mod regret;
+pub mod repl;
+pub mod replay;
+pub mod search;
+pub mod session;
+pub mod simulator;
+pub mod sizing;
+pub mod stats;
+pub mod tournament;
+pub mod versus;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use bet::Bet;
+pub use die::{Die, Faces, MAX_SIDES, MAX_TOTAL_WEIGHT};
+pub use error::SimulationError;
+pub use gold::Gold;
+
+// Numbers that can be averaged. Unlike `Into<f64>`, this takes in 64-bit
+// integers too, such as net gold, which only lose precision far past any
+// amount a game pays out.
+pub trait ToF64 {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! to_f64 {
+    ($($t:ty),*) => {
+        $(impl ToF64 for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+to_f64!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+pub trait Average {
+    // The mean, or `None` if there's nothing to average.
+    fn try_average(&mut self) -> Option<f64>;
+
+    // The mean, which is NaN if there's nothing to average.
+    fn average(&mut self) -> f64 {
+        self.try_average().unwrap_or(f64::NAN)
+    }
+
+    // The mean with each item counted in proportion to its weight. The weights
+    // needn't sum to 1, but there must be exactly one per item and they can't
+    // sum to 0.
+    fn weighted_average<W>(&mut self, weights: W) -> Result<f64, SimulationError>
+    where
+        W: IntoIterator,
+        W::Item: Into<f64>;
+}
+
+impl<Iter> Average for Iter
+where
+    Iter: Iterator,
+    Iter::Item: ToF64,
+{
+    fn try_average(&mut self) -> Option<f64> {
+        let mut sum = CompensatedSum::default();
+        let mut n = 0_u64;
+        for item in self {
+            sum.add(item.to_f64());
+            n += 1;
+        }
+        if n == 0 {
+            None
+        } else {
+            Some(sum.total() / n as f64)
+        }
+    }
+
+    fn weighted_average<W>(&mut self, weights: W) -> Result<f64, SimulationError>
+    where
+        W: IntoIterator,
+        W::Item: Into<f64>,
+    {
+        let mut weights = weights.into_iter();
+        let (mut sum, mut total_weight) = (CompensatedSum::default(), CompensatedSum::default());
+        let (mut items, mut weighted) = (0_usize, 0_usize);
+        loop {
+            match (self.next(), weights.next()) {
+                (Some(item), Some(weight)) => {
+                    let weight = weight.into();
+                    sum.add(weight * item.to_f64());
+                    total_weight.add(weight);
+                    items += 1;
+                    weighted += 1;
+                }
+                (Some(_), None) => items += 1,
+                (None, Some(_)) => weighted += 1,
+                (None, None) => break,
+            }
+        }
+        if items != weighted {
+            return Err(SimulationError::InvalidWeights(format!(
+                "{} weights for {} items",
+                weighted, items
+            )));
+        }
+        if total_weight.total() == 0.0 {
+            return Err(SimulationError::InvalidWeights(
+                "the weights sum to 0".to_owned(),
+            ));
+        }
+        Ok(sum.total() / total_weight.total())
+    }
+}
+
+// The mean of `value` over items counted by whole weights, like rolls by their
+// faces' weights. Dividing by the total weight once at the end keeps evenly
+// weighted items exactly as `average` would have them, so ties between powers
+// stay ties.
+pub fn weighted_mean<T>(
+    counts: impl IntoIterator<Item = (T, u64)>,
+    mut value: impl FnMut(T) -> f64,
+) -> f64 {
+    let (mut sum, mut total) = (CompensatedSum::default(), 0_u64);
+    for (item, weight) in counts {
+        sum.add(weight as f64 * value(item));
+        total += weight;
+    }
+    sum.total() / total as f64
+}
+
+// A running sum with Neumaier's compensation, which carries the low-order bits
+// a plain running sum drops, so means don't drift over long runs.
+#[derive(Default)]
+struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    fn add(&mut self, item: f64) {
+        let total = self.sum + item;
+        self.compensation += if self.sum.abs() >= item.abs() {
+            (self.sum - total) + item
+        } else {
+            (item - total) + self.sum
+        };
+        self.sum = total;
+    }
+
+    fn total(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+// What a winning roll pays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayoutRule {
+    Bet,
+    Sum,
+}
+
+impl fmt::Display for PayoutRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PayoutRule::Bet => "bet",
+            PayoutRule::Sum => "sum",
+        })
+    }
+}
+
+impl FromStr for PayoutRule {
+    type Err = SimulationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bet" => Ok(PayoutRule::Bet),
+            "sum" => Ok(PayoutRule::Sum),
+            _ => Err(SimulationError::UnknownPayoutRule(s.to_owned())),
+        }
+    }
+}
+
+// How the opening roll of a round is made: two dice, or three keeping the two
+// highest or lowest. Rerolls are always plain two-dice rolls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RollMode {
+    Normal,
+    Advantage,
+    Disadvantage,
+}
+
+impl RollMode {
+    pub fn roll<R: Rng + ?Sized>(self, faces: Faces, rng: &mut R) -> Dice {
+        match self {
+            RollMode::Normal => Dice::roll_with(faces, rng),
+            RollMode::Advantage => Dice::roll_with_advantage(faces, rng),
+            RollMode::Disadvantage => Dice::roll_with_disadvantage(faces, rng),
+        }
+    }
+
+    // Every opening roll, one for each ordered roll of two distinct faces or
+    // of three, with the product of those faces' weights.
+    pub fn counts(self, faces: Faces) -> Vec<(Dice, u64)> {
+        if self == RollMode::Normal {
+            return Dice::counts(faces).collect();
+        }
+        let counts: Vec<(u8, u32)> = faces.counts().collect();
+        let mut outcomes = Vec::with_capacity(counts.len().pow(3));
+        for &(a, wa) in &counts {
+            for &(b, wb) in &counts {
+                for &(c, wc) in &counts {
+                    let mut three = [a, b, c];
+                    three.sort_unstable();
+                    let (d1, d2) = match self {
+                        RollMode::Advantage => (three[1], three[2]),
+                        _ => (three[0], three[1]),
+                    };
+                    outcomes.push((
+                        Dice::sorted(Die::unchecked(d1), Die::unchecked(d2), faces),
+                        u64::from(wa) * u64::from(wb) * u64::from(wc),
+                    ));
+                }
+            }
+        }
+        outcomes
+    }
+
+    // Every opening roll as in `counts`, with its probability.
+    pub fn outcomes(self, faces: Faces) -> Vec<(Dice, f64)> {
+        let counts = self.counts(faces);
+        let total = counts.iter().map(|&(_, weight)| weight).sum::<u64>() as f64;
+        counts
+            .into_iter()
+            .map(|(dice, weight)| (dice, weight as f64 / total))
+            .collect()
+    }
+}
+
+impl fmt::Display for RollMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RollMode::Normal => "normal",
+            RollMode::Advantage => "advantage",
+            RollMode::Disadvantage => "disadvantage",
+        })
+    }
+}
+
+impl FromStr for RollMode {
+    type Err = SimulationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(RollMode::Normal),
+            "advantage" => Ok(RollMode::Advantage),
+            "disadvantage" => Ok(RollMode::Disadvantage),
+            _ => Err(SimulationError::UnknownRollMode(s.to_owned())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GameRules {
+    // Gold paid for a losing roll.
+    pub consolation: u8,
+    pub payout: PayoutRule,
+    pub faces: Faces,
+    pub roll: RollMode,
+    // With exploding dice, the highest bet allowed, since sums have no limit.
+    pub exploding: Option<u16>,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            consolation: 2,
+            payout: PayoutRule::Bet,
+            faces: Faces::default(),
+            roll: RollMode::Normal,
+            exploding: None,
+        }
+    }
+}
+
+// Dice are kept sorted, so equal rolls compare and hash equal in either order.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "DiceRecord", try_from = "DiceRecord")]
+pub struct Dice {
+    pub d1: Die,
+    pub d2: Die,
+    faces: Faces,
+    // What exploding dice added on top of the two faces.
+    bonus: u16,
+}
+
+// How dice are saved: the faces by their values, so they're checked again on
+// the way back in.
+#[derive(Serialize, Deserialize)]
+struct DiceRecord {
+    d1: u8,
+    d2: u8,
+    faces: String,
+    #[serde(default)]
+    bonus: u16,
+}
+
+impl From<Dice> for DiceRecord {
+    fn from(dice: Dice) -> Self {
+        Self {
+            d1: dice.d1.face(),
+            d2: dice.d2.face(),
+            faces: dice.faces.to_string(),
+            bonus: dice.bonus,
+        }
+    }
+}
+
+impl TryFrom<DiceRecord> for Dice {
+    type Error = SimulationError;
+
+    fn try_from(record: DiceRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            bonus: record.bonus,
+            ..Self::try_new(record.d1, record.d2, record.faces.parse()?)?
+        })
+    }
+}
+
+impl TryFrom<(u8, u8)> for Dice {
+    type Error = SimulationError;
+
+    fn try_from((d1, d2): (u8, u8)) -> Result<Self, Self::Error> {
+        Self::new(d1, d2)
+    }
+}
+
+impl Dice {
+    pub fn new(d1: u8, d2: u8) -> Result<Self, SimulationError> {
+        Ok(Self::sorted(
+            Die::new(d1, 6)?,
+            Die::new(d2, 6)?,
+            Faces::default(),
+        ))
+    }
+
+    // A roll of dice with any faces, in either order.
+    pub fn try_new(d1: u8, d2: u8, faces: Faces) -> Result<Self, SimulationError> {
+        Ok(Self::sorted(
+            Die::on(d1, faces)?,
+            Die::on(d2, faces)?,
+            faces,
+        ))
+    }
+
+    // For faces already known to be on the dice.
+    pub(crate) fn unchecked(d1: u8, d2: u8) -> Self {
+        Self::sorted(Die::unchecked(d1), Die::unchecked(d2), Faces::default())
+    }
+
+    pub(crate) fn sorted(d1: Die, d2: Die, faces: Faces) -> Self {
+        if d1 <= d2 {
+            Self {
+                d1,
+                d2,
+                faces,
+                bonus: 0,
+            }
+        } else {
+            Self {
+                d1: d2,
+                d2: d1,
+                faces,
+                bonus: 0,
+            }
+        }
+    }
+
+    pub fn roll() -> Self {
+        Self::roll_with_rng(&mut thread_rng())
+    }
+
+    pub fn roll_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::roll_with(Faces::default(), rng)
+    }
+
+    pub fn roll_with<R: Rng + ?Sized>(faces: Faces, rng: &mut R) -> Self {
+        Self::sorted(faces.roll(rng), faces.roll(rng), faces)
+    }
+
+    // `n` independent rolls, all from `rng`.
+    pub fn roll_many<R: Rng + ?Sized>(faces: Faces, rng: &mut R, n: usize) -> Vec<Self> {
+        iter::repeat_with(|| Self::roll_with(faces, rng))
+            .take(n)
+            .collect()
+    }
+
+    // Rolls three dice and keeps the two highest.
+    pub fn roll_with_advantage<R: Rng + ?Sized>(faces: Faces, rng: &mut R) -> Self {
+        let mut three = [faces.roll(rng), faces.roll(rng), faces.roll(rng)];
+        three.sort_unstable();
+        Self::sorted(three[1], three[2], faces)
+    }
+
+    // Rolls three dice and keeps the two lowest.
+    pub fn roll_with_disadvantage<R: Rng + ?Sized>(faces: Faces, rng: &mut R) -> Self {
+        let mut three = [faces.roll(rng), faces.roll(rng), faces.roll(rng)];
+        three.sort_unstable();
+        Self::sorted(three[0], three[1], faces)
+    }
+
+    pub fn faces(&self) -> Faces {
+        self.faces
+    }
+
+    // Under exploding rules, rolls again for each die showing its highest
+    // face, and again for each of those that does, adding every extra roll to
+    // the sum. The faces themselves keep the first roll, which is all a flip
+    // looks at. A die that can only land on its highest face never explodes,
+    // since it would never stop.
+    pub fn explode<R: Rng + ?Sized>(mut self, rules: &GameRules, rng: &mut R) -> Self {
+        let max = self.faces.max();
+        if rules.exploding.is_none() || self.faces.outcomes().all(|(face, _)| face == max) {
+            return self;
+        }
+        for die in [self.d1, self.d2] {
+            let mut face = die.face();
+            while face == max {
+                face = self.faces.roll(rng).face();
+                self.bonus = self.bonus.saturating_add(face.into());
+            }
+        }
+        self
+    }
+
+    // Wide enough that adding more or bigger dice can't overflow it.
+    pub fn sum(self) -> u16 {
+        u16::from(self.d1) + u16::from(self.d2) + self.bonus
+    }
+
+    // Every ordered roll of the two dice, each equally likely.
+    pub fn all_outcomes() -> impl Iterator<Item = Self> {
+        Self::outcomes(Faces::default()).map(|(dice, _)| dice)
+    }
+
+    // Every ordered roll of two distinct faces, with the product of their
+    // weights.
+    pub fn counts(faces: Faces) -> impl Iterator<Item = (Self, u64)> {
+        let counts: Vec<(u8, u32)> = faces.counts().collect();
+        (0..counts.len()).flat_map(move |i| {
+            let counts = counts.clone();
+            (0..counts.len()).map(move |j| {
+                let ((a, wa), (b, wb)) = (counts[i], counts[j]);
+                let dice = Self::sorted(Die::unchecked(a), Die::unchecked(b), faces);
+                (dice, u64::from(wa) * u64::from(wb))
+            })
+        })
+    }
+
+    // Every ordered roll of two distinct faces, with its probability.
+    pub fn outcomes(faces: Faces) -> impl Iterator<Item = (Self, f64)> {
+        let outcomes: Vec<(u8, f64)> = faces.outcomes().collect();
+        (0..outcomes.len()).flat_map(move |i| {
+            let outcomes = outcomes.clone();
+            (0..outcomes.len()).map(move |j| {
+                let ((a, pa), (b, pb)) = (outcomes[i], outcomes[j]);
+                (
+                    Self::sorted(Die::unchecked(a), Die::unchecked(b), faces),
+                    pa * pb,
+                )
+            })
+        })
+    }
+
+    // Probability that an unmodified opening roll meets or beats `bet`.
+    pub fn probability_of_winning(bet: Bet, rules: &GameRules) -> f64 {
+        weighted_mean(rules.roll.counts(rules.faces), |dice| {
+            f64::from(u8::from(bet <= dice.sum()))
+        })
+    }
+
+    // Expected gold from an unmodified roll.
+    pub fn average_gold(bet: Bet, rules: &GameRules) -> f64 {
+        weighted_mean(Self::counts(rules.faces), |dice| {
+            dice.gold(bet, rules).into()
+        })
+    }
+
+    pub fn modify(self, power: Power) -> Self {
+        match power {
+            Power::None => self,
+            Power::Reroll(dice) => dice,
+            Power::RerollKeepBest(dice) if dice.sum() > self.sum() => dice,
+            Power::RerollKeepBest(_) => self,
+            Power::FlipOne(target) => {
+                let target = self.faces.at_least(target);
+                if self.d1.face() < target {
+                    Self {
+                        bonus: self.bonus,
+                        ..Self::sorted(self.d1.flip_to(target, self.faces), self.d2, self.faces)
+                    }
+                } else {
+                    self
+                }
+            }
+        }
+    }
+
+    pub fn gold(self, bet: Bet, rules: &GameRules) -> Gold {
+        if bet <= self.sum() {
+            match rules.payout {
+                PayoutRule::Bet => bet.value().into(),
+                PayoutRule::Sum => self.sum().into(),
+            }
+        } else {
+            rules.consolation.into()
+        }
+    }
+
+    // Expected gold from using `power` on this roll at `bet`: just the gold
+    // for a flip or doing nothing, averaged over every new roll for a reroll.
+    pub fn expected_gold(self, bet: Bet, power: Power, rules: &GameRules) -> f64 {
+        power.expected_gold(self, bet, rules)
+    }
+}
+
+// The expected gold for each bet, in increasing order of bet, and how much a
+// single round's gold spreads around it. `std_dev` is NaN where the spread
+// isn't known, e.g. for antithetic pairs, and `min` and `max`, the least and
+// most gold seen in a round, are only known when sampling.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Outcome {
+    pub bets: Vec<Bet>,
+    pub ev: Vec<f64>,
+    pub std_dev: Vec<f64>,
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+}
+
+impl Outcome {
+    pub fn get(&self, bet: u16) -> Option<f64> {
+        self.bets.iter().position(|&b| b == bet).map(|i| self.ev[i])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Bet, f64)> + '_ {
+        self.bets.iter().copied().zip(self.ev.iter().copied())
+    }
+
+    // The expected gold given up on each bet, against `optimal`.
+    pub fn regret(&self, optimal: &Outcome) -> Vec<f64> {
+        self.iter()
+            .map(|(bet, ev)| optimal[bet.value()] - ev)
+            .collect()
+    }
+
+    // How much more expected gold this earns than `other` at each bet. The
+    // spread of a difference isn't known from the two spreads alone, so it's
+    // left NaN.
+    pub fn diff(&self, other: &Outcome) -> Result<Outcome, SimulationError> {
+        if self.bets != other.bets {
+            return Err(SimulationError::MismatchedBets);
+        }
+        Ok(self
+            .iter()
+            .zip(other.ev.iter())
+            .map(|((bet, ev), theirs)| (bet, ev - theirs))
+            .collect())
+    }
+
+    pub fn total_regret(&self, optimal: &Outcome) -> f64 {
+        self.regret(optimal).iter().sum()
+    }
+
+    // The spread relative to the mean at each bet: of two bets with similar
+    // EVs, the one with the higher coefficient is the riskier.
+    pub fn coefficient_of_variation(&self) -> Vec<f64> {
+        self.std_dev
+            .iter()
+            .zip(&self.ev)
+            .map(|(std_dev, ev)| std_dev / ev)
+            .collect()
+    }
+
+    // Only the bets `keep` accepts.
+    pub fn filter(&self, keep: impl Fn(Bet) -> bool) -> Outcome {
+        let mut outcome = Outcome::default();
+        for i in 0..self.bets.len() {
+            if keep(self.bets[i]) {
+                outcome.bets.push(self.bets[i]);
+                outcome.ev.push(self.ev[i]);
+                outcome.std_dev.push(self.std_dev[i]);
+                outcome.min.push(self.min[i]);
+                outcome.max.push(self.max[i]);
+            }
+        }
+        outcome
+    }
+
+    // A booktabs table to paste into a LaTeX document, with each bet's mean
+    // and, where it's known, its standard deviation.
+    pub fn to_latex(&self, caption: &str, label: &str) -> String {
+        let mut latex = String::new();
+        latex.push_str("\\begin{table}\n  \\centering\n  \\begin{tabular}{rr}\n");
+        latex.push_str("    \\toprule\n    Bet & Expected gold \\\\\n    \\midrule\n");
+        for ((bet, ev), &std_dev) in self.iter().zip(&self.std_dev) {
+            if std_dev.is_nan() {
+                latex.push_str(&format!("    {} & ${:.2}$ \\\\\n", bet, ev));
+            } else {
+                latex.push_str(&format!(
+                    "    {} & ${:.2} \\pm {:.2}$ \\\\\n",
+                    bet, ev, std_dev
+                ));
+            }
+        }
+        latex.push_str("    \\bottomrule\n  \\end{tabular}\n");
+        latex.push_str(&format!(
+            "  \\caption{{{}}}\n  \\label{{{}}}\n\\end{{table}}\n",
+            latex_escape(caption),
+            label
+        ));
+        latex
+    }
+
+    fn has_std_dev(&self) -> bool {
+        self.std_dev.iter().any(|std_dev| !std_dev.is_nan())
+    }
+
+    fn has_range(&self) -> bool {
+        self.min.iter().any(|min| !min.is_nan())
+    }
+}
+
+// Escapes the characters LaTeX treats specially in running text.
+fn latex_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '<' | '>' => escaped.push_str(&format!("${}$", c)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl FromIterator<(Bet, f64)> for Outcome {
+    fn from_iter<I: IntoIterator<Item = (Bet, f64)>>(iter: I) -> Self {
+        let (bets, ev): (Vec<_>, Vec<f64>) = iter.into_iter().unzip();
+        let unknown = vec![f64::NAN; ev.len()];
+        Outcome {
+            bets,
+            ev,
+            std_dev: unknown.clone(),
+            min: unknown.clone(),
+            max: unknown,
+        }
+    }
+}
+
+impl FromIterator<(Bet, RunningStats)> for Outcome {
+    fn from_iter<I: IntoIterator<Item = (Bet, RunningStats)>>(iter: I) -> Self {
+        let mut outcome = Outcome::default();
+        for (bet, stats) in iter {
+            outcome.bets.push(bet);
+            outcome.ev.push(stats.mean());
+            outcome.std_dev.push(stats.std_dev());
+            outcome.min.push(stats.min());
+            outcome.max.push(stats.max());
+        }
+        outcome
+    }
+}
+
+impl Index<u16> for Outcome {
+    type Output = f64;
+
+    fn index(&self, bet: u16) -> &f64 {
+        match (
+            self.bets.iter().position(|&b| b == bet),
+            self.bets.first(),
+            self.bets.last(),
+        ) {
+            (Some(i), _, _) => &self.ev[i],
+            (None, Some(min), Some(max)) => panic!("bet {} is outside of {}..={}", bet, min, max),
+            (None, _, _) => panic!("bet {} isn't in an outcome with no bets", bet),
+        }
+    }
+}
+
+// The probability of each net gold value from a round.
+#[derive(Debug)]
+pub struct GoldDistribution(pub BTreeMap<Gold, f64>);
+
+impl fmt::Display for GoldDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Gold | P")?;
+        writeln!(f, "---- | -")?;
+        for (gold, p) in &self.0 {
+            writeln!(f, " {:>3} | {:>8.4}", gold, p)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.d1, self.d2)?;
+        if self.bonus > 0 {
+            write!(f, "+{}", self.bonus)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.has_std_dev() {
+            writeln!(f, "Bet | Exp")?;
+            writeln!(f, "--- | ---")?;
+            for (bet, outcome) in self.iter() {
+                writeln!(f, " {:>2} | {:>16.2}", bet, outcome)?;
+            }
+            return Ok(());
+        }
+        let range = self.has_range();
+        if range {
+            writeln!(f, "Bet | Exp | SD | CV | Min | Max")?;
+            writeln!(f, "--- | --- | -- | -- | --- | ---")?;
+        } else {
+            writeln!(f, "Bet | Exp | SD | CV")?;
+            writeln!(f, "--- | --- | -- | --")?;
+        }
+        let spread = self.std_dev.iter().zip(self.coefficient_of_variation());
+        for (i, ((bet, outcome), (std_dev, cv))) in self.iter().zip(spread).enumerate() {
+            write!(
+                f,
+                " {:>2} | {:>16.2} | {:>8.2} | {:>6.2}",
+                bet, outcome, std_dev, cv
+            )?;
+            if range {
+                write!(f, " | {:>4} | {:>4}", self.min[i], self.max[i])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Power {
+    None,
+    // The new roll the dice are replaced with. Strategies only decide to
+    // reroll; `ApplicablePower::draw` draws the new roll.
+    Reroll(Dice),
+    // Like `Reroll`, but keeps whichever roll has the higher sum.
+    RerollKeepBest(Dice),
+    // Turns the lower die up to the target, or the closest face above it, but
+    // only if that raises it: a die already at or above the target is left be.
+    FlipOne(u8),
+}
+
+impl Power {
+    // The puzzle's flip, which turns a 1, 2, or 3 into a 4.
+    pub const FLIP: Power = Power::FlipOne(4);
+
+    // Rejects a flip to a face the die doesn't have. The puzzle's flip is
+    // exempt, so strategies built on it work with any faces.
+    pub fn check(self, faces: Faces) -> Result<Self, SimulationError> {
+        match self {
+            Power::FlipOne(target) if self != Power::FLIP && !faces.contains(target) => {
+                Err(SimulationError::InvalidFaces(format!(
+                    "can't flip to {}, which isn't one of {}",
+                    target, faces
+                )))
+            }
+            _ => Ok(self),
+        }
+    }
+}
+
+impl fmt::Display for Power {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Power::None => f.write_str("none"),
+            Power::Reroll(_) => f.write_str("reroll"),
+            Power::RerollKeepBest(_) => f.write_str("reroll-best"),
+            Power::FlipOne(4) => f.write_str("flip"),
+            Power::FlipOne(target) => write!(f, "flip:{}", target),
+        }
+    }
+}
+
+impl FromStr for Power {
+    type Err = SimulationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Power::None),
+            // Like a strategy's choice, the new roll is drawn when it's used.
+            "reroll" => Ok(Power::Reroll(Dice::unchecked(1, 1))),
+            "reroll-best" => Ok(Power::RerollKeepBest(Dice::unchecked(1, 1))),
+            "flip" => Ok(Power::FLIP),
+            _ => match s.strip_prefix("flip:").map(str::parse) {
+                Some(Ok(target)) if target > 0 => Ok(Power::FlipOne(target)),
+                _ => Err(SimulationError::UnknownPower(s.to_owned())),
+            },
+        }
+    }
+}
+
+// A set of powers a strategy can choose between, so other crates can define
+// their own alongside `Power`.
+pub trait ApplicablePower: Clone + fmt::Debug {
+    fn apply(self, dice: Dice) -> Dice;
+
+    // What happens when the power isn't available.
+    fn none() -> Self;
+
+    // Fills in any randomness the power needs before it's applied, like the
+    // new dice for a reroll.
+    fn draw(self, _rules: &GameRules, _rng: &mut dyn RngCore) -> Self {
+        self
+    }
+
+    fn cost(&self, _costs: &PowerCosts) -> Gold {
+        Gold::ZERO
+    }
+
+    // Takes one use of the power from `budget`, or returns false if none are
+    // left.
+    fn spend(&self, _budget: &mut PowerBudget) -> bool {
+        true
+    }
+
+    // Every roll using the power on `dice` could end on, with its probability,
+    // over any randomness `draw` would add.
+    fn outcomes(self, dice: Dice, _rules: &GameRules) -> Vec<(Dice, f64)> {
+        vec![(self.apply(dice), 1.0)]
+    }
+
+    // The expected gold from using the power on `dice`, over any randomness
+    // `draw` would add.
+    fn expected_gold(self, dice: Dice, bet: Bet, rules: &GameRules) -> f64 {
+        self.apply(dice).gold(bet, rules).into()
+    }
+
+    // Like `expected_gold`, given the expected gold from a fresh roll at
+    // `bet`, which is the same for every roll and so only needs working out
+    // once per bet.
+    fn expected_gold_given(self, dice: Dice, bet: Bet, rules: &GameRules, _fresh: f64) -> f64 {
+        self.expected_gold(dice, bet, rules)
+    }
+}
+
+impl ApplicablePower for Power {
+    fn apply(self, dice: Dice) -> Dice {
+        dice.modify(self)
+    }
+
+    fn none() -> Self {
+        Power::None
+    }
+
+    fn draw(self, rules: &GameRules, rng: &mut dyn RngCore) -> Self {
+        match self {
+            Power::Reroll(_) => {
+                Power::Reroll(Dice::roll_with(rules.faces, rng).explode(rules, rng))
+            }
+            Power::RerollKeepBest(_) => {
+                Power::RerollKeepBest(Dice::roll_with(rules.faces, rng).explode(rules, rng))
+            }
+            power => power,
+        }
+    }
+
+    fn cost(&self, costs: &PowerCosts) -> Gold {
+        costs.cost(self)
+    }
+
+    fn outcomes(self, dice: Dice, rules: &GameRules) -> Vec<(Dice, f64)> {
+        match self {
+            Power::Reroll(_) | Power::RerollKeepBest(_) => Dice::outcomes(rules.faces)
+                .map(|(new, p)| {
+                    let power = match self {
+                        Power::Reroll(_) => Power::Reroll(new),
+                        _ => Power::RerollKeepBest(new),
+                    };
+                    (dice.modify(power), p)
+                })
+                .collect(),
+            power => vec![(dice.modify(power), 1.0)],
+        }
+    }
+
+    fn spend(&self, budget: &mut PowerBudget) -> bool {
+        let remaining = match self {
+            Power::None => return true,
+            Power::Reroll(_) | Power::RerollKeepBest(_) => &mut budget.rerolls_remaining,
+            Power::FlipOne(_) => &mut budget.flips_remaining,
+        };
+        match *remaining {
+            0 => false,
+            PowerBudget::UNLIMITED => true,
+            _ => {
+                *remaining -= 1;
+                true
+            }
+        }
+    }
+
+    fn expected_gold(self, dice: Dice, bet: Bet, rules: &GameRules) -> f64 {
+        match self {
+            Power::Reroll(_) => Dice::average_gold(bet, rules),
+            Power::RerollKeepBest(_) => weighted_mean(Dice::counts(rules.faces), |new| {
+                dice.modify(Power::RerollKeepBest(new))
+                    .gold(bet, rules)
+                    .into()
+            }),
+            power => dice.modify(power).gold(bet, rules).into(),
+        }
+    }
+
+    fn expected_gold_given(self, dice: Dice, bet: Bet, rules: &GameRules, fresh: f64) -> f64 {
+        match self {
+            Power::Reroll(_) => fresh,
+            power => power.expected_gold(dice, bet, rules),
+        }
+    }
+}
+
+pub fn check_probability(p: f64) -> Result<f64, SimulationError> {
+    if (0.0..=1.0).contains(&p) {
+        Ok(p)
+    } else {
+        Err(SimulationError::InvalidProbability(p))
+    }
+}
+
+// The most gold, either way, that a cost or a starting bankroll can be. Far
+// more than any game needs, and small enough that a session of `u32::MAX`
+// rounds can't overflow an `i64`.
+pub const MAX_GOLD: i64 = 1_000_000_000;
+
+pub fn check_gold(what: &'static str, gold: i64) -> Result<i64, SimulationError> {
+    if (-MAX_GOLD..=MAX_GOLD).contains(&gold) {
+        Ok(gold)
+    } else {
+        Err(SimulationError::GoldOutOfRange { what, gold })
+    }
+}
+
+// The gold charged each time a power is used.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PowerCosts {
+    pub reroll: i64,
+    pub flip: i64,
+}
+
+impl PowerCosts {
+    pub fn check(self) -> Result<Self, SimulationError> {
+        check_gold("reroll cost", self.reroll)?;
+        check_gold("flip cost", self.flip)?;
+        Ok(self)
+    }
+
+    pub fn cost(&self, power: &Power) -> Gold {
+        match power {
+            Power::None => Gold::ZERO,
+            Power::Reroll(_) | Power::RerollKeepBest(_) => Gold(self.reroll),
+            Power::FlipOne(_) => Gold(self.flip),
+        }
+    }
+}
+
+// How many more times each power can be used, where `UNLIMITED` is never used
+// up. A strategy starts each trial, or each session, with its `budget`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PowerBudget {
+    pub rerolls_remaining: u8,
+    pub flips_remaining: u8,
+}
+
+impl PowerBudget {
+    pub const UNLIMITED: u8 = u8::MAX;
+
+    pub fn unlimited() -> Self {
+        Self {
+            rerolls_remaining: Self::UNLIMITED,
+            flips_remaining: Self::UNLIMITED,
+        }
+    }
+}
+
+impl Default for PowerBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+// Everything that happened in one round, from the roll to the payout.
+#[derive(Clone, Copy, Debug)]
+pub struct Round<P = Power> {
+    pub bet: Bet,
+    pub rolled: Dice,
+    pub power: P,
+    pub dice: Dice,
+    pub won: bool,
+    pub gold: Gold,
+    pub cost: Gold,
+}
+
+impl<P> Round<P> {
+    pub fn net(&self) -> Gold {
+        self.gold - self.cost
+    }
+}
+
+// Like `bet=9 rolled (3,5)=8 -> reroll -> (6,6)=12 -> gold 9`.
+impl<P: fmt::Display> fmt::Display for Round<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bet={} rolled {}={} -> {} -> {}={} -> gold {}",
+            self.bet,
+            self.rolled,
+            self.rolled.sum(),
+            self.power,
+            self.dice,
+            self.dice.sum(),
+            self.gold
+        )?;
+        if self.cost != Gold::ZERO {
+            write!(f, " (cost {})", self.cost)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Mode {
+    Exact,
+    MonteCarlo(u64),
+    // Sampling in mirrored pairs of rounds; see `antithetic::pair_outcome`.
+    Antithetic(u64),
+}
+
+// How often long simulations report their progress, in trials.
+pub const REPORT_EVERY: u64 = 100_000;
+
+pub trait Strategy<P: ApplicablePower = Power> {
+    fn name(&self) -> &str;
+
+    // A name that stays put for looking the strategy up, like its type's
+    // name, even if `name` is reworded.
+    fn id(&self) -> &str {
+        self.name()
+    }
+
+    // What the strategy does, in a sentence.
+    fn description(&self) -> &'static str {
+        ""
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> P;
+
+    // Whether `choose_power` always makes the same choice for the same bet and
+    // roll. Only then can outcomes be enumerated exactly; the reroll itself is
+    // fine, since it's just another uniform roll.
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    // The power uses available at the start of each trial or session.
+    fn budget(&self) -> PowerBudget {
+        PowerBudget::unlimited()
+    }
+
+    // Like `choose_power`, but never picks a power that's run out. By default
+    // that means doing nothing instead.
+    fn choose_power_within(&self, bet: Bet, dice: Dice, budget: &PowerBudget) -> P {
+        let power = self.choose_power(bet, dice);
+        if power.spend(&mut budget.clone()) {
+            power
+        } else {
+            P::none()
+        }
+    }
+
+    // Computes outcomes exactly or by sampling, falling back to sampling a
+    // million trials when `Mode::Exact` is asked of a nondeterministic strategy.
+    fn outcome_mode(
+        &self,
+        mode: Mode,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Result<Outcome, SimulationError> {
+        match mode {
+            Mode::Exact if self.is_deterministic() => {
+                self.exact_outcome(rules, power_availability, costs)
+            }
+            Mode::Exact => self.avg_outcome(1_000_000, rules, power_availability, costs, rng),
+            Mode::MonteCarlo(trials) => {
+                self.avg_outcome(trials, rules, power_availability, costs, rng)
+            }
+            Mode::Antithetic(trials) => {
+                self.antithetic_outcome(trials, rules, power_availability, costs, rng)
+            }
+        }
+    }
+
+    // A single round with a fresh `budget`.
+    fn round(
+        &self,
+        bet: Bet,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Round<P> {
+        self.round_within(
+            bet,
+            rules,
+            power_availability,
+            costs,
+            &mut self.budget(),
+            rng,
+        )
+    }
+
+    // The power is only charged with probability `power_availability`; when it
+    // isn't, the roll stands without consulting the strategy. Any power used
+    // comes out of `budget`.
+    fn round_within(
+        &self,
+        bet: Bet,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        budget: &mut PowerBudget,
+        rng: &mut dyn RngCore,
+    ) -> Round<P> {
+        let rolled = rules.roll.roll(rules.faces, rng).explode(rules, rng);
+        let available = power_availability >= 1.0
+            || (power_availability > 0.0 && rng.gen_bool(power_availability));
+        let power = if available {
+            match self.choose_power_within(bet, rolled, budget) {
+                power if power.spend(budget) => power.draw(rules, rng),
+                _ => P::none(),
+            }
+        } else {
+            P::none()
+        };
+        let cost = power.cost(costs);
+        let dice = power.clone().apply(rolled);
+        Round {
+            bet,
+            rolled,
+            power,
+            dice,
+            won: bet <= dice.sum(),
+            gold: dice.gold(bet, rules),
+            cost,
+        }
+    }
+
+    // The net gold from a single round.
+    fn outcome(
+        &self,
+        bet: Bet,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Gold {
+        self.round(bet, rules, power_availability, costs, rng).net()
+    }
+
+    // An endless stream of independent rounds' net gold for `bet`, drawn from
+    // `rng`.
+    fn outcome_iter<'a, R: RngCore + 'a>(
+        &'a self,
+        bet: Bet,
+        rules: &'a GameRules,
+        power_availability: f64,
+        costs: &'a PowerCosts,
+        mut rng: R,
+    ) -> impl Iterator<Item = Gold> + 'a
+    where
+        Self: Sized,
+    {
+        iter::repeat_with(move || self.outcome(bet, rules, power_availability, costs, &mut rng))
+    }
+
+    fn avg_outcome(
+        &self,
+        trials: u64,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Result<Outcome, SimulationError> {
+        self.avg_outcome_reporting(
+            trials,
+            rules,
+            power_availability,
+            costs,
+            &mut |_, _| {},
+            rng,
+        )
+    }
+
+    // Like `avg_outcome`, but tells `report` how many trials of each bet have
+    // run, every `REPORT_EVERY` trials and once the bet is done.
+    fn avg_outcome_reporting(
+        &self,
+        trials: u64,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        report: &mut dyn FnMut(Bet, u64),
+        rng: &mut dyn RngCore,
+    ) -> Result<Outcome, SimulationError> {
+        if trials == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        check_probability(power_availability)?;
+        Ok(rules
+            .bets()
+            .map(|bet| {
+                let mut stats = RunningStats::default();
+                while stats.count() < trials {
+                    let chunk = REPORT_EVERY.min(trials - stats.count());
+                    for _ in 0..chunk {
+                        stats.push(
+                            self.outcome(bet, rules, power_availability, costs, rng)
+                                .into(),
+                        );
+                    }
+                    report(bet, stats.count());
+                }
+                (bet, stats)
+            })
+            .collect())
+    }
+
+    // Like `avg_outcome`, but plays every bet each trial and hands the running
+    // averages to `progress` after every `report_every` trials, so long runs
+    // can show whether they've settled. A `report_every` of 0 never reports.
+    #[allow(clippy::too_many_arguments)]
+    fn avg_outcome_with_progress(
+        &self,
+        trials: u64,
+        report_every: u64,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        progress: &mut dyn FnMut(u64, &Outcome),
+        rng: &mut dyn RngCore,
+    ) -> Result<Outcome, SimulationError> {
+        if trials == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        check_probability(power_availability)?;
+        let mut stats: Vec<(Bet, RunningStats)> = rules
+            .bets()
+            .map(|bet| (bet, RunningStats::default()))
+            .collect();
+        let averages =
+            |stats: &[(Bet, RunningStats)]| -> Outcome { stats.iter().copied().collect() };
+        for n in 1..=trials {
+            for (bet, stats) in &mut stats {
+                stats.push(
+                    self.outcome(*bet, rules, power_availability, costs, rng)
+                        .into(),
+                );
+            }
+            if report_every != 0 && n % report_every == 0 && n != trials {
+                progress(n, &averages(&stats));
+            }
+        }
+        Ok(averages(&stats))
+    }
+
+    // Like `avg_outcome`, but plays `trials` rounds as mirrored pairs.
+    fn antithetic_outcome(
+        &self,
+        trials: u64,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Result<Outcome, SimulationError> {
+        if trials == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        check_probability(power_availability)?;
+        Ok(rules
+            .bets()
+            .map(|bet| {
+                let average = iter::repeat_with(|| {
+                    antithetic::pair_outcome(self, bet, rules, power_availability, costs, rng)
+                })
+                .take(trials.div_ceil(2) as usize)
+                .average();
+                (bet, average)
+            })
+            .collect())
+    }
+
+    // A `1 - alpha` confidence interval for the mean gold at `bet`, read off
+    // the `alpha / 2` and `1 - alpha / 2` quantiles of the means of
+    // `n_bootstrap` resamples of `trials` rounds. Unlike the normal
+    // approximation, it assumes nothing about the shape of the payouts, but
+    // takes `trials * n_bootstrap` draws.
+    #[allow(clippy::too_many_arguments)]
+    fn bootstrap_confidence_interval(
+        &self,
+        trials: u64,
+        bet: Bet,
+        n_bootstrap: u32,
+        alpha: f64,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Result<(f64, f64), SimulationError> {
+        if trials == 0 || n_bootstrap == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+            return Err(SimulationError::InvalidProbability(alpha));
+        }
+        check_probability(power_availability)?;
+        let samples: Vec<Gold> = (0..trials)
+            .map(|_| self.outcome(bet, rules, power_availability, costs, rng))
+            .collect();
+        let mut means: Vec<f64> = (0..n_bootstrap)
+            .map(|_| {
+                f64::from(
+                    (0..trials)
+                        .map(|_| samples[rng.gen_range(0, samples.len())])
+                        .sum::<Gold>(),
+                ) / trials as f64
+            })
+            .collect();
+        means.sort_by(|a, b| {
+            a.partial_cmp(b)
+                .expect("means of finite gold are never NaN")
+        });
+        // Nearest-rank quantiles.
+        let quantile = |q: f64| means[((q * means.len() as f64).ceil() as usize).max(1) - 1];
+        Ok((quantile(alpha / 2.0), quantile(1.0 - alpha / 2.0)))
+    }
+
+    // The probability of each net gold value at `bet`: summed over every roll
+    // for deterministic strategies in exact mode, or else a histogram of
+    // sampled rounds.
+    fn gold_distribution(
+        &self,
+        bet: Bet,
+        mode: Mode,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Result<BTreeMap<Gold, f64>, SimulationError> {
+        check_probability(power_availability)?;
+        let trials = match mode {
+            Mode::Exact if self.is_deterministic() => {
+                rules.check_enumerable()?;
+                return Ok(self.exact_distribution(bet, rules, power_availability, costs));
+            }
+            Mode::Exact => 1_000_000,
+            Mode::MonteCarlo(trials) | Mode::Antithetic(trials) => trials,
+        };
+        if trials == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        let mut counts = BTreeMap::new();
+        for _ in 0..trials {
+            *counts
+                .entry(self.outcome(bet, rules, power_availability, costs, rng))
+                .or_insert(0) += 1;
+        }
+        Ok(counts
+            .into_iter()
+            .map(|(gold, count)| (gold, count as f64 / trials as f64))
+            .collect())
+    }
+
+    // The sampled chance of a round's net gold at `bet` being at most each
+    // value seen, in increasing order of gold.
+    fn empirical_cdf(
+        &self,
+        bet: Bet,
+        trials: u64,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<(Gold, f64)>, SimulationError> {
+        let distribution = self.gold_distribution(
+            bet,
+            Mode::MonteCarlo(trials),
+            rules,
+            power_availability,
+            costs,
+            rng,
+        )?;
+        let mut total = 0.0;
+        Ok(distribution
+            .into_iter()
+            .map(|(gold, p)| {
+                total += p;
+                (gold, total)
+            })
+            .collect())
+    }
+
+    fn exact_distribution(
+        &self,
+        bet: Bet,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+    ) -> BTreeMap<Gold, f64> {
+        let mut distribution = BTreeMap::new();
+        for (dice, p) in rules.roll.outcomes(rules.faces) {
+            let power = self.choose_power_within(bet, dice, &self.budget());
+            let cost = power.cost(costs);
+            for (end, q) in power.outcomes(dice, rules) {
+                *distribution
+                    .entry(end.gold(bet, rules) - cost)
+                    .or_insert(0.0) += power_availability * p * q;
+            }
+            if power_availability < 1.0 {
+                *distribution.entry(dice.gold(bet, rules)).or_insert(0.0) +=
+                    (1.0 - power_availability) * p;
+            }
+        }
+        distribution
+    }
+
+    // The exact counterpart of `avg_outcome` for the standard game: default
+    // rules, a power that's always charged, and no power costs.
+    fn exact_ev_table(&self) -> Outcome {
+        self.exact_outcome(&GameRules::default(), 1.0, &PowerCosts::default())
+            .expect("the standard game is always valid")
+    }
+
+    // The expected net gold for each bet, summed over every roll rather than
+    // sampled, mixing the powered and unpowered rounds by `power_availability`.
+    fn exact_outcome(
+        &self,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+    ) -> Result<Outcome, SimulationError> {
+        check_probability(power_availability)?;
+        rules.check_enumerable()?;
+        let mut outcome = Outcome::default();
+        let rolls = rules.roll.counts(rules.faces);
+        for bet in rules.bets() {
+            let fresh = Dice::average_gold(bet, rules);
+            let average = weighted_mean(rolls.iter().copied(), |dice| {
+                let power = self.choose_power_within(bet, dice, &self.budget());
+                let cost = f64::from(power.cost(costs));
+                let powered = power.expected_gold_given(dice, bet, rules, fresh) - cost;
+                power_availability * powered
+                    + (1.0 - power_availability) * f64::from(dice.gold(bet, rules))
+            });
+            let square = weighted_mean(rolls.iter().copied(), |dice| {
+                let power = self.choose_power_within(bet, dice, &self.budget());
+                let cost = f64::from(power.cost(costs));
+                let powered: f64 = power
+                    .outcomes(dice, rules)
+                    .into_iter()
+                    .map(|(end, p)| p * (f64::from(end.gold(bet, rules)) - cost).powi(2))
+                    .sum();
+                power_availability * powered
+                    + (1.0 - power_availability) * f64::from(dice.gold(bet, rules)).powi(2)
+            });
+            outcome.bets.push(bet);
+            outcome.ev.push(average);
+            outcome
+                .std_dev
+                .push((square - average * average).max(0.0).sqrt());
+            outcome.min.push(f64::NAN);
+            outcome.max.push(f64::NAN);
+        }
+        Ok(outcome)
+    }
+}
+
+impl<P: ApplicablePower, S: Strategy<P> + ?Sized> Strategy<P> for &S {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn id(&self) -> &str {
+        (**self).id()
+    }
+
+    fn description(&self) -> &'static str {
+        (**self).description()
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> P {
+        (**self).choose_power(bet, dice)
+    }
+
+    fn is_deterministic(&self) -> bool {
+        (**self).is_deterministic()
+    }
+
+    fn budget(&self) -> PowerBudget {
+        (**self).budget()
+    }
+
+    fn choose_power_within(&self, bet: Bet, dice: Dice, budget: &PowerBudget) -> P {
+        (**self).choose_power_within(bet, dice, budget)
+    }
+
+    fn round_within(
+        &self,
+        bet: Bet,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        budget: &mut PowerBudget,
+        rng: &mut dyn RngCore,
+    ) -> Round<P> {
+        (**self).round_within(bet, rules, power_availability, costs, budget, rng)
+    }
+}
+
+pub struct RerollIfLosing();
+impl Strategy for RerollIfLosing {
+    fn name(&self) -> &str {
+        "Reroll if losing"
+    }
+
+    fn id(&self) -> &str {
+        "RerollIfLosing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rerolls whenever the roll is below the bet."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        if dice.sum() < bet {
+            Power::Reroll(dice)
+        } else {
+            Power::None
+        }
+    }
+}
+
+// A baseline for how much `RerollIfLosing` gains by only rerolling a losing
+// roll: this throws away winning rolls at low bets.
+pub struct AlwaysReroll();
+impl Strategy for AlwaysReroll {
+    fn name(&self) -> &str {
+        "Always reroll"
+    }
+
+    fn id(&self) -> &str {
+        "AlwaysReroll"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rerolls every roll, win or lose."
+    }
+
+    fn choose_power(&self, _bet: Bet, dice: Dice) -> Power {
+        Power::Reroll(dice)
+    }
+}
+
+// Rerolling only when losing would gain nothing from keeping the better roll:
+// a losing roll is never better than the reroll. Always rerolling can, though,
+// and it never does worse than `RerollIfLosing`.
+pub struct AlwaysRerollKeepBest();
+impl Strategy for AlwaysRerollKeepBest {
+    fn name(&self) -> &str {
+        "Always reroll, keeping the better roll"
+    }
+
+    fn id(&self) -> &str {
+        "AlwaysRerollKeepBest"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rerolls every roll and keeps whichever sum is higher."
+    }
+
+    fn choose_power(&self, _bet: Bet, dice: Dice) -> Power {
+        Power::RerollKeepBest(dice)
+    }
+}
+
+// `RerollIfLosing` with only so many uses of each power.
+pub struct BudgetedRerollIfLosing(pub PowerBudget);
+impl Strategy for BudgetedRerollIfLosing {
+    fn name(&self) -> &str {
+        "Reroll if losing, within a budget"
+    }
+
+    fn id(&self) -> &str {
+        "BudgetedRerollIfLosing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rerolls when losing until the --reroll-budget runs out."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        RerollIfLosing().choose_power(bet, dice)
+    }
+
+    fn budget(&self) -> PowerBudget {
+        self.0
+    }
+}
+
+// Uses the first strategy's power, or the second's when the first would do
+// nothing.
+pub struct FallbackStrategy<S1: Strategy, S2: Strategy>(pub S1, pub S2);
+impl<S1: Strategy, S2: Strategy> Strategy for FallbackStrategy<S1, S2> {
+    fn name(&self) -> &str {
+        "One strategy, falling back to another"
+    }
+
+    fn description(&self) -> &'static str {
+        "Uses the first strategy's power, or the second's when the first does nothing."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        match self.0.choose_power(bet, dice) {
+            Power::None => self.1.choose_power(bet, dice),
+            power => power,
+        }
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.0.is_deterministic() && self.1.is_deterministic()
+    }
+}
+
+// Plays the first strategy with probability `p` each round, and otherwise the
+// second, drawing from the round's own RNG so seeded runs repeat. The name
+// includes `p`.
+pub struct MixedStrategy<S1: Strategy, S2: Strategy> {
+    first: S1,
+    second: S2,
+    p: f64,
+    name: String,
+}
+
+impl<S1: Strategy, S2: Strategy> MixedStrategy<S1, S2> {
+    pub fn new(first: S1, second: S2, p: f64) -> Result<Self, SimulationError> {
+        check_probability(p)?;
+        let name = format!(
+            "{} with probability {}, else {}",
+            first.name(),
+            p,
+            second.name()
+        );
+        Ok(Self {
+            first,
+            second,
+            p,
+            name,
+        })
+    }
+}
+
+impl<S1: Strategy, S2: Strategy> Strategy for MixedStrategy<S1, S2> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &'static str {
+        "Plays one strategy with some probability each round, and otherwise another."
+    }
+
+    // Without an RNG to pick with, the more likely strategy's choice. Rounds
+    // pick at random in `round_within` instead.
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        if self.p >= 0.5 {
+            self.first.choose_power(bet, dice)
+        } else {
+            self.second.choose_power(bet, dice)
+        }
+    }
+
+    fn is_deterministic(&self) -> bool {
+        match self.p {
+            1.0 => self.first.is_deterministic(),
+            0.0 => self.second.is_deterministic(),
+            _ => false,
+        }
+    }
+
+    // Each power can only be used as often as both strategies allow.
+    fn budget(&self) -> PowerBudget {
+        let (first, second) = (self.first.budget(), self.second.budget());
+        PowerBudget {
+            rerolls_remaining: first.rerolls_remaining.min(second.rerolls_remaining),
+            flips_remaining: first.flips_remaining.min(second.flips_remaining),
+        }
+    }
+
+    fn round_within(
+        &self,
+        bet: Bet,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        budget: &mut PowerBudget,
+        rng: &mut dyn RngCore,
+    ) -> Round {
+        if rng.gen_bool(self.p) {
+            self.first
+                .round_within(bet, rules, power_availability, costs, budget, rng)
+        } else {
+            self.second
+                .round_within(bet, rules, power_availability, costs, budget, rng)
+        }
+    }
+}
+
+pub struct RerollIfLosingOrFlip();
+impl Strategy for RerollIfLosingOrFlip {
+    fn name(&self) -> &str {
+        "If losing, flip (if applicable) or reroll"
+    }
+
+    fn id(&self) -> &str {
+        "RerollIfLosingOrFlip"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flips when that wins, and otherwise rerolls when losing."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        if bet <= dice.sum() {
+            Power::None
+        } else if bet <= dice.modify(Power::FLIP).sum() {
+            Power::FLIP
+        } else {
+            Power::Reroll(dice)
+        }
+    }
+}
+
+// Every strategy that doesn't need a parameter, in the order they're reported.
+// `MarkovOptimal` only knows the standard game's dice and bets.
+pub fn strategies(rules: &GameRules, costs: &PowerCosts) -> Vec<Box<dyn Strategy>> {
+    let mut strategies: Vec<Box<dyn Strategy>> = vec![
+        Box::new(NoPower()),
+        Box::new(RerollIfLosing()),
+        Box::new(AlwaysReroll()),
+        Box::new(AlwaysRerollKeepBest()),
+        Box::new(AlwaysFlip()),
+        Box::new(RerollIfLosingOrFlip()),
+        Box::new(GreedyBestPower(*rules, *costs)),
+        Box::new(ConservativeReroll::new(0.0, *rules)),
+        Box::new(ConservativeReroll::new(0.5, *rules)),
+        Box::new(ConservativeReroll::new(1.0, *rules)),
+    ];
+    if rules.faces == Faces::default() && rules.exploding.is_none() {
+        strategies.push(Box::new(markov::MarkovOptimal(*costs)));
+    }
+    strategies
+}
+
+// Looks a strategy up by its id, like `RerollIfLosing`, or by its name, like
+// "Reroll if losing", ignoring case, spaces, and punctuation.
+pub fn find_strategy<'a>(
+    strategies: &'a [Box<dyn Strategy>],
+    name: &str,
+) -> Result<&'a dyn Strategy, SimulationError> {
+    let squash = |name: &str| {
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+    strategies
+        .iter()
+        .find(|strategy| {
+            squash(strategy.id()) == squash(name) || squash(strategy.name()) == squash(name)
+        })
+        .map(|strategy| &**strategy)
+        .ok_or_else(|| SimulationError::UnknownStrategy(name.to_owned()))
+}
+
+pub struct AlwaysFlip();
+impl Strategy for AlwaysFlip {
+    fn name(&self) -> &str {
+        "Flip if sum is < 5"
+    }
+
+    fn id(&self) -> &str {
+        "AlwaysFlip"
+    }
+
+    fn description(&self) -> &'static str {
+        "Always flips, turning a 1, 2, or 3 into a 4."
+    }
+
+    fn choose_power(&self, _bet: Bet, _dice: Dice) -> Power {
+        Power::FLIP
+    }
+}
+
+pub struct NoPower();
+impl Strategy for NoPower {
+    fn name(&self) -> &str {
+        "No change"
+    }
+
+    fn id(&self) -> &str {
+        "NoPower"
+    }
+
+    fn description(&self) -> &'static str {
+        "Never uses a power."
+    }
+
+    fn choose_power(&self, _bet: Bet, _dice: Dice) -> Power {
+        Power::None
+    }
+}
+
+// Picks whichever power has the highest expected gold from this roll net of its
+// cost, treating a reroll as a fresh unmodified roll.
+pub struct GreedyBestPower(pub GameRules, pub PowerCosts);
+impl Strategy for GreedyBestPower {
+    fn name(&self) -> &str {
+        "Greedy best power"
+    }
+
+    fn id(&self) -> &str {
+        "GreedyBestPower"
+    }
+
+    fn description(&self) -> &'static str {
+        "Uses whichever power has the highest expected gold on the roll."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        let reroll = Dice::average_gold(bet, &self.0) - self.1.reroll as f64;
+        let flip = f64::from(dice.modify(Power::FLIP).gold(bet, &self.0)) - self.1.flip as f64;
+        let none = f64::from(dice.gold(bet, &self.0));
+        if none >= flip && none >= reroll {
+            Power::None
+        } else if flip >= reroll {
+            Power::FLIP
+        } else {
+            Power::Reroll(dice)
+        }
+    }
+}
+
+// Rerolls only when that's expected to earn more than `min_gain` gold over
+// keeping the roll. With a `min_gain` of 0 that's any losing roll that could
+// still win, like `RerollIfLosing`. The name includes `min_gain`.
+pub struct ConservativeReroll {
+    min_gain: f64,
+    rules: GameRules,
+    name: String,
+}
+
+impl ConservativeReroll {
+    pub fn new(min_gain: f64, rules: GameRules) -> Self {
+        let name = format!("Reroll if it gains more than {} gold", min_gain);
+        Self {
+            min_gain,
+            rules,
+            name,
+        }
+    }
+}
+
+impl Strategy for ConservativeReroll {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn id(&self) -> &str {
+        "ConservativeReroll"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rerolls only when the expected gain over keeping the roll is large enough."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        let gain = dice.expected_gold(bet, Power::Reroll(dice), &self.rules)
+            - f64::from(dice.gold(bet, &self.rules));
+        if gain > self.min_gain {
+            Power::Reroll(dice)
+        } else {
+            Power::None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+
+    use proptest::prelude::{any, prop_assert, proptest};
+
+    use super::*;
+
+    #[test]
+    fn dice_stay_on_the_die() {
+        assert!(Dice::new(1, 6).is_ok());
+        for (d1, d2) in [(0, 3), (3, 0), (7, 1), (1, 7), (0, 0)] {
+            assert!(matches!(
+                Dice::new(d1, d2),
+                Err(SimulationError::InvalidDieFace { sides: 6, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn dice_are_sorted() {
+        let dice = Dice::new(6, 1).unwrap();
+        assert_eq!(dice.d1.face(), 1);
+        assert_eq!(dice.d2.face(), 6);
+    }
+
+    #[test]
+    fn dice_in_either_order_are_equal() {
+        let hash = |dice: Dice| {
+            let mut hasher = DefaultHasher::new();
+            dice.hash(&mut hasher);
+            hasher.finish()
+        };
+        let (a, b) = (Dice::new(3, 4).unwrap(), Dice::new(4, 3).unwrap());
+        assert_eq!(a, b);
+        assert_eq!(hash(a), hash(b));
+        assert_ne!(a, Dice::new(3, 5).unwrap());
+        let counts = Dice::all_outcomes().fold(HashMap::new(), |mut counts, dice| {
+            *counts.entry(dice).or_insert(0) += 1;
+            counts
+        });
+        assert_eq!(counts.len(), 21);
+        assert_eq!(counts[&a], 2);
+    }
+
+    #[test]
+    fn sum_adds_both_dice() {
+        assert_eq!(Dice::new(1, 1).unwrap().sum(), 2);
+        assert_eq!(Dice::new(6, 3).unwrap().sum(), 9);
+        assert_eq!(Dice::new(6, 6).unwrap().sum(), 12);
+    }
+
+    #[test]
+    fn roll_many_repeats_with_a_seed() {
+        let roll =
+            |seed, n| Dice::roll_many(Faces::default(), &mut simulator::rng_from(Some(seed)), n);
+        assert!(roll(1, 0).is_empty());
+        assert_eq!(roll(1, 500).len(), 500);
+        assert_eq!(roll(1, 500), roll(1, 500));
+        assert_ne!(roll(1, 500), roll(2, 500));
+        // One rng throughout, so a shorter batch is the start of a longer one.
+        assert_eq!(roll(1, 100), roll(1, 500)[..100]);
+    }
+
+    #[test]
+    fn gold_pays_the_bet_or_the_consolation() {
+        let rules = GameRules::default();
+        let by_sum = GameRules {
+            payout: PayoutRule::Sum,
+            ..GameRules::default()
+        };
+        let generous = GameRules {
+            consolation: 5,
+            ..GameRules::default()
+        };
+        for (bet, (d1, d2), rules, gold) in [
+            (7, (3, 4), &rules, 7),
+            (7, (6, 6), &rules, 7),
+            (7, (3, 3), &rules, 2),
+            (2, (1, 1), &rules, 2),
+            (12, (6, 6), &rules, 12),
+            (12, (6, 5), &rules, 2),
+            (7, (3, 4), &by_sum, 7),
+            (7, (6, 5), &by_sum, 11),
+            (7, (2, 3), &by_sum, 2),
+            (9, (4, 5), &generous, 9),
+            (9, (4, 4), &generous, 5),
+        ] {
+            let bet = Bet::new(bet, rules).unwrap();
+            let dice = Dice::new(d1, d2).unwrap();
+            assert_eq!(dice.gold(bet, rules), Gold(gold), "{:?} at {}", dice, bet);
+        }
+    }
+
+    #[test]
+    fn powers_round_trip_through_strings() {
+        for power in [
+            Power::None,
+            Power::Reroll(Dice::unchecked(1, 1)),
+            Power::RerollKeepBest(Dice::unchecked(1, 1)),
+            Power::FLIP,
+            Power::FlipOne(6),
+        ] {
+            assert_eq!(power.to_string().parse::<Power>().unwrap(), power);
+        }
+        for name in ["none", "reroll", "reroll-best", "flip", "flip:2"] {
+            assert_eq!(name.parse::<Power>().unwrap().to_string(), name);
+        }
+        for name in ["", "Reroll", "flip:0", "flip:x", "swap"] {
+            assert!(matches!(
+                name.parse::<Power>(),
+                Err(SimulationError::UnknownPower(_))
+            ));
+        }
+    }
+
+    // From 1 and 2 at bet 7, a reroll wins on 21 of 36 rolls, so it's worth
+    // (21 * 7 + 15 * 2) / 36 = 59/12.
+    #[test]
+    fn expected_gold_of_each_power() {
+        let rules = GameRules::default();
+        let bet = Bet::new(7, &rules).unwrap();
+        let dice = Dice::new(1, 2).unwrap();
+        for power in [Power::Reroll(dice), Power::RerollKeepBest(dice)] {
+            assert!((dice.expected_gold(bet, power, &rules) - 59.0 / 12.0).abs() < 1e-12);
+        }
+        assert_eq!(dice.expected_gold(bet, Power::None, &rules), 2.0);
+        assert_eq!(dice.expected_gold(bet, Power::FLIP, &rules), 2.0);
+        let close = Dice::new(2, 3).unwrap();
+        assert_eq!(close.expected_gold(bet, Power::FLIP, &rules), 7.0);
+    }
+
+    #[test]
+    fn strategies_are_found_by_type_name() {
+        let strategies = strategies(&GameRules::default(), &PowerCosts::default());
+        for strategy in &strategies {
+            assert_eq!(
+                find_strategy(&strategies, strategy.id()).unwrap().id(),
+                strategy.id()
+            );
+            assert_eq!(
+                find_strategy(&strategies, strategy.name()).unwrap().name(),
+                strategy.name()
+            );
+        }
+        let ids: Vec<&str> = strategies.iter().map(|strategy| strategy.id()).collect();
+        for id in [
+            "NoPower",
+            "RerollIfLosing",
+            "AlwaysReroll",
+            "AlwaysRerollKeepBest",
+            "AlwaysFlip",
+            "RerollIfLosingOrFlip",
+            "GreedyBestPower",
+            "ConservativeReroll",
+            "MarkovOptimal",
+        ] {
+            assert!(ids.contains(&id), "{}", id);
+            assert_eq!(find_strategy(&strategies, id).unwrap().id(), id);
+        }
+        assert_eq!(
+            find_strategy(&strategies, "no-power").unwrap().id(),
+            "NoPower"
+        );
+        assert_eq!(
+            find_strategy(&strategies, "No change").unwrap().id(),
+            "NoPower"
+        );
+        assert!(matches!(
+            find_strategy(&strategies, "Nothing"),
+            Err(SimulationError::UnknownStrategy(_))
+        ));
+    }
+
+    #[test]
+    fn zero_trials_is_an_error() {
+        let (rules, costs) = (GameRules::default(), PowerCosts::default());
+        let mut rng = simulator::rng_from(Some(1));
+        assert!(matches!(
+            NoPower().avg_outcome(0, &rules, 1.0, &costs, &mut rng),
+            Err(SimulationError::InvalidTrialCount)
+        ));
+        assert!(matches!(
+            simulator::SimulationBuilder::default()
+                .trials(0)
+                .run(&NoPower()),
+            Err(SimulationError::InvalidTrialCount)
+        ));
+    }
+
+    // With the power always available, a round is just roll, choose, and
+    // apply; with it never available, every strategy plays like `NoPower`.
+    #[test]
+    fn power_availability_gates_the_strategy() {
+        let (rules, costs) = (GameRules::default(), PowerCosts::default());
+        let bet = Bet::new(8, &rules).unwrap();
+        let (mut a, mut b) = (simulator::rng_from(Some(5)), simulator::rng_from(Some(5)));
+        for _ in 0..1000 {
+            let rolled = Dice::roll_with_rng(&mut b);
+            let power = RerollIfLosingOrFlip()
+                .choose_power(bet, rolled)
+                .draw(&rules, &mut b);
+            assert_eq!(
+                RerollIfLosingOrFlip().outcome(bet, &rules, 1.0, &costs, &mut a),
+                rolled.modify(power).gold(bet, &rules)
+            );
+        }
+        let (mut a, mut b) = (simulator::rng_from(Some(6)), simulator::rng_from(Some(6)));
+        for _ in 0..1000 {
+            assert_eq!(
+                RerollIfLosingOrFlip().outcome(bet, &rules, 0.0, &costs, &mut a),
+                NoPower().outcome(bet, &rules, 1.0, &costs, &mut b)
+            );
+        }
+    }
+
+    #[test]
+    fn distribution_sums_to_one_around_the_exact_mean() {
+        let (rules, costs) = (GameRules::default(), PowerCosts::default());
+        let bet = Bet::new(7, &rules).unwrap();
+        let mut rng = simulator::rng_from(Some(8));
+        let exact = NoPower()
+            .gold_distribution(bet, Mode::Exact, &rules, 1.0, &costs, &mut rng)
+            .unwrap();
+        assert_eq!(
+            exact.keys().copied().collect::<Vec<_>>(),
+            [Gold(2), Gold(7)]
+        );
+        assert!((exact.values().sum::<f64>() - 1.0).abs() < 1e-12);
+        let mean: f64 = exact.iter().map(|(&gold, p)| f64::from(gold) * p).sum();
+        let expected = NoPower().exact_outcome(&rules, 1.0, &costs).unwrap()[7];
+        assert!((mean - expected).abs() < 1e-12);
+
+        let sampled = NoPower()
+            .gold_distribution(bet, Mode::MonteCarlo(10_000), &rules, 1.0, &costs, &mut rng)
+            .unwrap();
+        assert!((sampled.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((sampled[&Gold(7)] - exact[&Gold(7)]).abs() < 0.02);
+    }
+
+    #[test]
+    fn observed_gold_stays_between_the_consolation_and_the_bet() {
+        let (rules, costs) = (GameRules::default(), PowerCosts::default());
+        let mut rng = simulator::rng_from(Some(10));
+        for strategy in strategies(&rules, &costs) {
+            let outcome = strategy
+                .avg_outcome(2_000, &rules, 1.0, &costs, &mut rng)
+                .unwrap();
+            for (i, bet) in outcome.bets.iter().enumerate() {
+                let (min, max) = (outcome.min[i], outcome.max[i]);
+                assert!(min >= f64::from(rules.consolation), "{} at {}", min, bet);
+                assert!(max <= f64::from(bet.value()), "{} at {}", max, bet);
+                assert!(min <= outcome.ev[i] && outcome.ev[i] <= max);
+            }
+        }
+    }
+
+    // Each face is enumerated once however heavy it is, so a die weighted 50
+    // to 50 is no slower to solve than a coin.
+    #[test]
+    fn heavy_weights_solve_quickly() {
+        let costs = PowerCosts::default();
+        for roll in [RollMode::Normal, RollMode::Advantage] {
+            let rules = GameRules {
+                faces: "1*50,6*50".parse().unwrap(),
+                roll,
+                ..GameRules::default()
+            };
+            for strategy in strategies(&rules, &costs) {
+                strategy.exact_outcome(&rules, 1.0, &costs).unwrap();
+            }
+            // Double sixes come up a quarter of the time, or, keeping the
+            // best two of three, half the time.
+            let p = if roll == RollMode::Normal { 0.25 } else { 0.5 };
+            let outcome = NoPower().exact_outcome(&rules, 1.0, &costs).unwrap();
+            assert!((outcome[12] - (p * 12.0 + (1.0 - p) * 2.0)).abs() < 1e-12);
+        }
+    }
+
+    proptest! {
+        // Whatever the bet, seed, and power availability, the sampled mean
+        // should be within five standard errors of the exact one; at four,
+        // about one run in a hundred of proptest's 256 cases would fail.
+        #[test]
+        fn sampled_ev_is_near_exact(
+            flip in any::<bool>(),
+            bet in 2..=12u16,
+            seed in any::<u64>(),
+            power_availability in 0.0..=1.0,
+        ) {
+            let (rules, costs) = (GameRules::default(), PowerCosts::default());
+            let trials = 20_000;
+            let strategy: &dyn Strategy = if flip { &AlwaysFlip() } else { &NoPower() };
+            let bet = Bet::new(bet, &rules).unwrap();
+            let mut rng = simulator::rng_from(Some(seed));
+            let simulated = (0..trials)
+                .map(|_| strategy.outcome(bet, &rules, power_availability, &costs, &mut rng))
+                .map(f64::from)
+                .sum::<f64>()
+                / trials as f64;
+            let exact = strategy
+                .exact_outcome(&rules, power_availability, &costs)
+                .unwrap();
+            let i = exact.bets.iter().position(|&b| b == bet).unwrap();
+            let std_err = exact.std_dev[i] / (trials as f64).sqrt();
+            prop_assert!(
+                (simulated - exact.ev[i]).abs() <= 5.0 * std_err + 1e-9,
+                "{} at bet {}: {} vs {}",
+                strategy.name(),
+                bet,
+                simulated,
+                exact.ev[i]
+            );
+        }
+    }
+}
+
+// 1. Bet a number // bet
+// 2. roll two 6-sided dice. Dice::roll()
+// 2.a. If you roll the number or higher, you get that many gold. Dice::gold(bet, rules)
+// 2.b. If you roll lower than the number, you get 2 gold.
+// What number should you bet for the best expected return?
+// You can get a power in the game that lets you either
+// 1. Reroll both dice once
+// 2. flip one die to show 4.
+// 3. Do nothing
+// With that power, what number should you bet for the best expected return?
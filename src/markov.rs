@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+use serde::Serialize;
+
+use crate::{
+    ApplicablePower, Bet, Dice, Faces, GameRules, GreedyBestPower, Power, PowerCosts,
+    SimulationError, Strategy,
+};
+
+// The power with the most expected gold on `dice` at `bet`, net of its cost.
+// Ties go to the earlier of doing nothing, rerolling, rerolling and keeping
+// the better roll, and flipping.
+pub fn best_power(dice: Dice, bet: Bet, rules: &GameRules, costs: &PowerCosts) -> Power {
+    let powers = [
+        Power::None,
+        Power::Reroll(Dice::unchecked(1, 1)),
+        Power::RerollKeepBest(Dice::unchecked(1, 1)),
+        Power::FLIP,
+    ];
+    let mut best = Power::None;
+    let mut most = f64::NEG_INFINITY;
+    for &power in &powers {
+        let gold = power.expected_gold(dice, bet, rules) - f64::from(power.cost(costs));
+        if gold > most {
+            most = gold;
+            best = power;
+        }
+    }
+    best
+}
+
+// The best power for every roll of two standard dice at every bet of the
+// standard game, with free powers: `table[6 * (d1 - 1) + (d2 - 1)][bet - 2]`.
+pub fn compute_value_table() -> [[Power; 11]; 36] {
+    let rules = GameRules::default();
+    let mut table = [[Power::None; 11]; 36];
+    for d1 in 1..=6 {
+        for d2 in 1..=6 {
+            let dice = Dice::unchecked(d1, d2);
+            let row = &mut table[6 * usize::from(d1 - 1) + usize::from(d2 - 1)];
+            for (bet, best) in rules.bets().zip(row.iter_mut()) {
+                *best = best_power(dice, bet, &rules, &PowerCosts::default());
+            }
+        }
+    }
+    table
+}
+
+static VALUE_TABLE: LazyLock<[[Power; 11]; 36]> = LazyLock::new(compute_value_table);
+
+// Plays the standard game optimally, roll by roll, net of what each power
+// costs. With free powers each choice is looked up in a table worked out once;
+// otherwise it's worked out on every roll. Other dice have no entries, so it
+// does nothing with them.
+pub struct MarkovOptimal(pub PowerCosts);
+impl Strategy for MarkovOptimal {
+    fn name(&self) -> &str {
+        "Markov optimal"
+    }
+
+    fn id(&self) -> &str {
+        "MarkovOptimal"
+    }
+
+    fn description(&self) -> &'static str {
+        "Looks up the best power for the standard game in a precomputed table."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        if dice.faces() != Faces::default() {
+            return Power::None;
+        }
+        if self.0 != PowerCosts::default() {
+            return best_power(dice, bet, &GameRules::default(), &self.0);
+        }
+        let index = 6 * (dice.d1.face() - 1) + (dice.d2.face() - 1);
+        VALUE_TABLE[usize::from(index)]
+            .get(usize::from(bet.value() - 2))
+            .copied()
+            .unwrap_or(Power::None)
+    }
+}
+
+// What to do with every roll at every bet, for reading rather than playing:
+// `N` does nothing, `F` flips, and `R` rerolls, whichever has the most
+// expected gold net of its cost. Ties go to doing nothing, then flipping, as in
+// `GreedyBestPower`, so a roll that already wins shows `N`. Rows are the first
+// die and columns the second.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DecisionTable {
+    pub faces: Vec<u8>,
+    pub bets: BTreeMap<u16, Vec<Vec<char>>>,
+}
+
+impl DecisionTable {
+    pub fn new(rules: &GameRules, costs: &PowerCosts) -> Result<Self, SimulationError> {
+        let strategy = GreedyBestPower(*rules, *costs);
+        let faces = rules.faces.values().to_vec();
+        let mut bets = BTreeMap::new();
+        for bet in rules.bets() {
+            let grid = faces
+                .iter()
+                .map(|&d1| {
+                    faces
+                        .iter()
+                        .map(|&d2| {
+                            let dice = Dice::try_new(d1, d2, rules.faces)?;
+                            Ok(match strategy.choose_power(bet, dice) {
+                                Power::None => 'N',
+                                Power::FlipOne(_) => 'F',
+                                Power::Reroll(_) | Power::RerollKeepBest(_) => 'R',
+                            })
+                        })
+                        .collect::<Result<Vec<_>, SimulationError>>()
+                })
+                .collect::<Result<Vec<_>, SimulationError>>()?;
+            bets.insert(bet.value(), grid);
+        }
+        Ok(Self { faces, bets })
+    }
+}
+
+impl fmt::Display for DecisionTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "N: do nothing, F: flip a die up to 4, R: reroll both dice"
+        )?;
+        for (bet, grid) in &self.bets {
+            writeln!(f)?;
+            writeln!(f, "Bet {}:", bet)?;
+            write!(f, "   ")?;
+            for value in &self.faces {
+                write!(f, " {:>2}", value)?;
+            }
+            writeln!(f)?;
+            for (d1, row) in self.faces.iter().zip(grid) {
+                write!(f, " {:>2}", d1)?;
+                for choice in row {
+                    write!(f, " {:>2}", choice)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regret::optimal_outcome;
+
+    #[test]
+    fn markov_optimal_pays_for_its_powers() {
+        let rules = GameRules::default();
+        for costs in [
+            PowerCosts::default(),
+            PowerCosts { reroll: 1, flip: 0 },
+            PowerCosts { reroll: 0, flip: 3 },
+        ] {
+            let optimal = optimal_outcome(&rules, 1.0, &costs).unwrap();
+            let markov = MarkovOptimal(costs)
+                .exact_outcome(&rules, 1.0, &costs)
+                .unwrap();
+            for (bet, ev) in optimal.iter() {
+                assert!((markov[bet.value()] - ev).abs() < 1e-9, "bet {}", bet);
+            }
+        }
+    }
+
+    #[test]
+    fn costly_rerolls_are_skipped() {
+        let rules = GameRules::default();
+        let bet = Bet::new(8, &rules).unwrap();
+        let dice = Dice::unchecked(1, 2);
+        let free = PowerCosts::default();
+        let dear = PowerCosts {
+            reroll: 10,
+            flip: 0,
+        };
+        assert_eq!(
+            MarkovOptimal(free).choose_power(bet, dice),
+            Power::Reroll(Dice::unchecked(1, 1))
+        );
+        assert_eq!(MarkovOptimal(dear).choose_power(bet, dice), Power::None);
+        assert_eq!(
+            GreedyBestPower(rules, dear).choose_power(bet, dice),
+            Power::None
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use rand::RngCore;
+
+use crate::{check_probability, Bet, GameRules, PowerCosts, SimulationError, Strategy};
+
+// Every player stakes their bet, rolls, and uses a power as their strategy
+// sees fit against their own bet; the highest sum takes the whole pot, split
+// evenly on a tie. Returns each player's expected net gold per round: their
+// share of the pot less their stake and any power costs.
+#[allow(clippy::too_many_arguments)]
+pub fn multiplayer_simulation(
+    n_players: u32,
+    bets: Vec<u16>,
+    strategies: Vec<Box<dyn Strategy>>,
+    trials: u64,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+    rng: &mut dyn RngCore,
+) -> Result<Vec<f64>, SimulationError> {
+    let players = n_players as usize;
+    if players == 0 || bets.len() != players || strategies.len() != players {
+        return Err(SimulationError::InvalidPlayers(format!(
+            "{} players with {} bets and {} strategies",
+            n_players,
+            bets.len(),
+            strategies.len()
+        )));
+    }
+    if trials == 0 {
+        return Err(SimulationError::InvalidTrialCount);
+    }
+    check_probability(power_availability)?;
+    let bets = bets
+        .into_iter()
+        .map(|bet| Bet::new(bet, rules))
+        .collect::<Result<Vec<_>, _>>()?;
+    let pot: i64 = bets.iter().map(|bet| i64::from(bet.value())).sum();
+    let mut totals = vec![0.0; players];
+    for _ in 0..trials {
+        let rounds: Vec<_> = bets
+            .iter()
+            .zip(&strategies)
+            .map(|(&bet, strategy)| strategy.round(bet, rules, power_availability, costs, rng))
+            .collect();
+        let high = rounds
+            .iter()
+            .map(|round| round.dice.sum())
+            .max()
+            .unwrap_or(0);
+        let winners = rounds
+            .iter()
+            .filter(|round| round.dice.sum() == high)
+            .count();
+        for (total, round) in totals.iter_mut().zip(&rounds) {
+            let share = if round.dice.sum() == high {
+                pot as f64 / winners as f64
+            } else {
+                0.0
+            };
+            *total += share - f64::from(round.bet.value()) - f64::from(round.cost);
+        }
+    }
+    Ok(totals
+        .into_iter()
+        .map(|total| total / trials as f64)
+        .collect())
+}
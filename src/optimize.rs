@@ -0,0 +1,127 @@
+use std::fmt;
+
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+
+use crate::stats::RunningStats;
+use crate::{
+    check_probability, Bet, Dice, GameRules, Outcome, Power, PowerCosts, SimulationError, Strategy,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThresholdParams {
+    // How far short of the bet a roll has to fall to be rerolled.
+    pub margin: u8,
+    // Losing rolls with a lower sum are flipped, if that wins.
+    pub flip_below: u8,
+}
+
+impl fmt::Display for ThresholdParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "margin {}, flip below {}", self.margin, self.flip_below)
+    }
+}
+
+// Every margin from 0 to 6 with every flip threshold from 2 to 12.
+pub fn threshold_grid() -> Vec<ThresholdParams> {
+    (0..=6)
+        .flat_map(|margin| (2..=12).map(move |flip_below| ThresholdParams { margin, flip_below }))
+        .collect()
+}
+
+// Flips a losing roll below `flip_below` when the flip wins, and otherwise
+// rerolls one that misses the bet by more than `margin`. A margin of 0
+// rerolls every losing roll, like `RerollIfLosing`.
+pub struct ThresholdReroll(pub ThresholdParams);
+impl Strategy for ThresholdReroll {
+    fn name(&self) -> &str {
+        "Threshold reroll"
+    }
+
+    fn id(&self) -> &str {
+        "ThresholdReroll"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flips below a threshold when that wins, and rerolls when losing by more than a margin."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        let sum = dice.sum();
+        if bet <= sum {
+            Power::None
+        } else if sum < u16::from(self.0.flip_below) && bet <= dice.modify(Power::FLIP).sum() {
+            Power::FLIP
+        } else if sum + u16::from(self.0.margin) < bet.value() {
+            Power::Reroll(dice)
+        } else {
+            Power::None
+        }
+    }
+}
+
+// Plays the strategy `build` makes from each point of `grid` at every bet.
+// Every candidate sees the same rolls: trial `i` of each bet is seeded the
+// same way for all of them, so differences between candidates aren't just
+// noise.
+#[allow(clippy::too_many_arguments)]
+pub fn grid_search<P: Copy, S: Strategy>(
+    grid: &[P],
+    build: impl Fn(P) -> S,
+    trials: u64,
+    seed: u64,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+) -> Result<Vec<(P, Outcome)>, SimulationError> {
+    if trials == 0 {
+        return Err(SimulationError::InvalidTrialCount);
+    }
+    check_probability(power_availability)?;
+    Ok(grid
+        .iter()
+        .map(|&params| {
+            let strategy = build(params);
+            let outcome = rules
+                .bets()
+                .map(|bet| {
+                    let mut master = SmallRng::seed_from_u64(seed ^ u64::from(bet.value()));
+                    let mut stats = RunningStats::default();
+                    for _ in 0..trials {
+                        let mut rng = SmallRng::seed_from_u64(master.gen());
+                        stats.push(
+                            strategy
+                                .outcome(bet, rules, power_availability, costs, &mut rng)
+                                .into(),
+                        );
+                    }
+                    (bet, stats)
+                })
+                .collect();
+            (params, outcome)
+        })
+        .collect())
+}
+
+// Every candidate's expected gold at each bet, one row per candidate.
+pub struct GridCsv<'a, P>(pub &'a [(P, Outcome)]);
+
+impl<P: fmt::Display> fmt::Display for GridCsv<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parameters")?;
+        if let Some((_, outcome)) = self.0.first() {
+            for bet in &outcome.bets {
+                write!(f, ",{}", bet)?;
+            }
+        }
+        writeln!(f)?;
+        for (params, outcome) in self.0 {
+            write!(f, "\"{}\"", params)?;
+            for ev in &outcome.ev {
+                write!(f, ",{:.4}", ev)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
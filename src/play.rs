@@ -0,0 +1,101 @@
+use std::io::{BufRead, Write};
+
+use rand::RngCore;
+
+use crate::{ApplicablePower, Bet, GameRules, Gold, Power, PowerCosts, SimulationError};
+
+// Plays the game interactively over `input` and `output`, one round at a time,
+// until the player quits. Returns the total gold won.
+pub fn play<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    rules: &GameRules,
+    costs: &PowerCosts,
+    rng: &mut dyn RngCore,
+) -> Result<Gold, SimulationError> {
+    let range = rules.bet_range();
+    let mut total = Gold::ZERO;
+    loop {
+        let question = format!("Bet ({}..={}, or q to quit): ", range.start(), range.end());
+        let bet = match ask(&mut input, &mut output, &question, |answer| {
+            answer
+                .parse()
+                .ok()
+                .and_then(|bet| Bet::new(bet, rules).ok())
+        })? {
+            Some(bet) => bet,
+            None => break,
+        };
+        let dice = rules.roll.roll(rules.faces, rng).explode(rules, rng);
+        let faces = u16::from(dice.d1) + u16::from(dice.d2);
+        if dice.sum() > faces {
+            writeln!(
+                output,
+                "You rolled {} and {}, exploding to {}.",
+                dice.d1,
+                dice.d2,
+                dice.sum()
+            )?;
+        } else {
+            writeln!(output, "You rolled {} and {}.", dice.d1, dice.d2)?;
+        }
+        let power = match ask(
+            &mut input,
+            &mut output,
+            "Power (none, reroll, reroll-best, flip, flip:FACE, or q to quit): ",
+            |answer| {
+                answer
+                    .parse::<Power>()
+                    .ok()
+                    .and_then(|power| power.check(rules.faces).ok())
+            },
+        )? {
+            Some(power) => power.draw(rules, rng),
+            None => break,
+        };
+        let modified = power.apply(dice);
+        if modified != dice || matches!(power, Power::Reroll(_) | Power::RerollKeepBest(_)) {
+            writeln!(output, "Now you have {} and {}.", modified.d1, modified.d2)?;
+        }
+        let gold = modified.gold(bet, rules) - power.cost(costs);
+        total = total.checked_add(gold)?;
+        writeln!(
+            output,
+            "You {} with {} against {}, for {} gold. Total: {}",
+            if bet <= modified.sum() { "win" } else { "lose" },
+            modified.sum(),
+            bet,
+            gold,
+            total
+        )?;
+    }
+    writeln!(output, "You leave with {} gold.", total)?;
+    Ok(total)
+}
+
+// Asks `question` until `parse` accepts the answer. `None` means the player
+// quit, either with `q` or by closing the input.
+fn ask<R: BufRead, W: Write, T>(
+    input: &mut R,
+    output: &mut W,
+    question: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Option<T>, SimulationError> {
+    loop {
+        write!(output, "{}", question)?;
+        output.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            writeln!(output)?;
+            return Ok(None);
+        }
+        let answer = answer.trim();
+        if answer.eq_ignore_ascii_case("q") {
+            return Ok(None);
+        }
+        match parse(answer) {
+            Some(value) => return Ok(Some(value)),
+            None => writeln!(output, "{:?} isn't a valid choice.", answer)?,
+        }
+    }
+}
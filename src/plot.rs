@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::{Outcome, SimulationError};
+
+// Draws each strategy's EV against the bet as a line, with a legend, to an SVG
+// or PNG depending on the extension of `path`.
+pub fn plot_outcomes(outcomes: &[(String, Outcome)], path: &Path) -> Result<(), SimulationError> {
+    let size = (1024, 768);
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("svg") => draw(outcomes, SVGBackend::new(path, size).into_drawing_area()),
+        Some("png") => draw(outcomes, BitMapBackend::new(path, size).into_drawing_area()),
+        _ => Err(SimulationError::UnknownPlotFormat(
+            path.display().to_string(),
+        )),
+    }
+}
+
+fn draw<B: DrawingBackend>(
+    outcomes: &[(String, Outcome)],
+    area: DrawingArea<B, Shift>,
+) -> Result<(), SimulationError> {
+    let error = |err: DrawingAreaErrorKind<B::ErrorType>| SimulationError::Plot(err.to_string());
+    let points = || outcomes.iter().flat_map(|(_, outcome)| outcome.iter());
+    let bets = || points().map(|(bet, _)| u32::from(bet.value()));
+    let (min_bet, max_bet) = (bets().min().unwrap_or(2), bets().max().unwrap_or(12));
+    let min_ev = points().map(|(_, ev)| ev).fold(0.0, f64::min);
+    let max_ev = points().map(|(_, ev)| ev).fold(0.0, f64::max);
+
+    area.fill(&WHITE).map_err(error)?;
+    let mut chart = ChartBuilder::on(&area)
+        .caption("Expected gold by bet", ("sans-serif", 24))
+        .margin(16)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(min_bet..max_bet, min_ev..max_ev * 1.05)
+        .map_err(error)?;
+    chart
+        .configure_mesh()
+        .x_desc("Bet")
+        .y_desc("Expected gold")
+        .draw()
+        .map_err(error)?;
+    for (i, (name, outcome)) in outcomes.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        chart
+            .draw_series(LineSeries::new(
+                outcome.iter().map(|(bet, ev)| (u32::from(bet.value()), ev)),
+                color.stroke_width(2),
+            ))
+            .map_err(error)?
+            .label(name.as_str())
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(3))
+            });
+    }
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .map_err(error)?;
+    area.present().map_err(error)
+}
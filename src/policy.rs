@@ -0,0 +1,130 @@
+use std::fmt;
+use std::iter;
+
+use rand::prelude::*;
+
+use crate::{
+    antithetic, check_probability, Average, Bet, Dice, GameRules, Mode, Power, PowerBudget,
+    PowerCosts, Round, SimulationError, Strategy,
+};
+
+// A complete policy: it picks its own bet as well as which power to use.
+pub trait BettingStrategy: Strategy {
+    fn choose_bet(&self, rules: &GameRules) -> Bet;
+
+    fn label(&self, rules: &GameRules) -> String {
+        format!("{}, betting {}", self.name(), self.choose_bet(rules))
+    }
+}
+
+// Any strategy, always betting the same number.
+pub struct WithBet<S> {
+    pub strategy: S,
+    pub bet: Bet,
+}
+
+impl<S: Strategy> Strategy for WithBet<S> {
+    fn name(&self) -> &str {
+        self.strategy.name()
+    }
+
+    fn id(&self) -> &str {
+        self.strategy.id()
+    }
+
+    fn description(&self) -> &'static str {
+        self.strategy.description()
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        self.strategy.choose_power(bet, dice)
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.strategy.is_deterministic()
+    }
+
+    fn budget(&self) -> PowerBudget {
+        self.strategy.budget()
+    }
+
+    fn choose_power_within(&self, bet: Bet, dice: Dice, budget: &PowerBudget) -> Power {
+        self.strategy.choose_power_within(bet, dice, budget)
+    }
+
+    fn round_within(
+        &self,
+        bet: Bet,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        budget: &mut PowerBudget,
+        rng: &mut dyn RngCore,
+    ) -> Round {
+        self.strategy
+            .round_within(bet, rules, power_availability, costs, budget, rng)
+    }
+}
+
+impl<S: Strategy> BettingStrategy for WithBet<S> {
+    fn choose_bet(&self, _rules: &GameRules) -> Bet {
+        self.bet
+    }
+}
+
+// The expected gold per round from playing `policy`.
+pub fn expected_gold<P: BettingStrategy + ?Sized>(
+    policy: &P,
+    mode: Mode,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+    rng: &mut dyn RngCore,
+) -> Result<f64, SimulationError> {
+    let bet = policy.choose_bet(rules);
+    match mode {
+        Mode::Exact if policy.is_deterministic() => {
+            Ok(policy.exact_outcome(rules, power_availability, costs)?[bet.value()])
+        }
+        Mode::Exact => expected_gold(
+            policy,
+            Mode::MonteCarlo(1_000_000),
+            rules,
+            power_availability,
+            costs,
+            rng,
+        ),
+        Mode::MonteCarlo(0) => Err(SimulationError::InvalidTrialCount),
+        Mode::MonteCarlo(trials) => {
+            check_probability(power_availability)?;
+            Ok(
+                iter::repeat_with(|| policy.outcome(bet, rules, power_availability, costs, rng))
+                    .take(trials as usize)
+                    .average(),
+            )
+        }
+        Mode::Antithetic(0) => Err(SimulationError::InvalidTrialCount),
+        Mode::Antithetic(trials) => {
+            check_probability(power_availability)?;
+            Ok(iter::repeat_with(|| {
+                antithetic::pair_outcome(policy, bet, rules, power_availability, costs, rng)
+            })
+            .take(trials.div_ceil(2) as usize)
+            .average())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PolicyOutcome(pub Vec<(String, f64)>);
+
+impl fmt::Display for PolicyOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Policy | Exp")?;
+        writeln!(f, "------ | ---")?;
+        for (label, outcome) in &self.0 {
+            writeln!(f, " {:<52} | {:>8.2}", label, outcome)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use std::io::{self, Write};
+use std::time::Instant;
+
+use crate::Bet;
+
+// A one-line progress bar on stderr for a strategy's simulation, redrawn in
+// place each time a bet reports.
+pub struct ProgressBar {
+    name: String,
+    bets: u64,
+    trials: u64,
+    started: Instant,
+}
+
+const WIDTH: usize = 30;
+
+impl ProgressBar {
+    pub fn new(name: &str, bets: u64, trials: u64) -> Self {
+        Self {
+            name: name.to_owned(),
+            bets,
+            trials,
+            started: Instant::now(),
+        }
+    }
+
+    // `bet_index` is how many bets finished before `bet`, and `done` how many
+    // of `bet`'s trials have run.
+    pub fn update(&self, bet_index: u64, bet: Bet, done: u64) {
+        let total = (self.bets * self.trials) as f64;
+        let fraction = (bet_index * self.trials + done) as f64 / total;
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let eta = elapsed / fraction - elapsed;
+        let filled = (fraction * WIDTH as f64) as usize;
+        let _ = write!(
+            io::stderr(),
+            "\r{}: bet {:>2} [{}{}] {:>3.0}% ETA {:.0}s ",
+            self.name,
+            bet,
+            "#".repeat(filled),
+            " ".repeat(WIDTH - filled),
+            fraction * 100.0,
+            eta
+        );
+    }
+
+    // Erases the bar.
+    pub fn finish(&self) {
+        let _ = write!(io::stderr(), "\r\x1b[2K");
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::{GameRules, Mode, PowerCosts};
+
+// How a set of results was produced, so a saved report can be told apart from
+// another run's later on. Fields serialize in declaration order, so two runs'
+// metadata diff line by line.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Provenance {
+    pub version: &'static str,
+    // Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub sampling: &'static str,
+    // None for exact solutions.
+    pub trials: Option<u64>,
+    pub seed: Option<u64>,
+    pub consolation: u8,
+    pub payout: String,
+    pub faces: String,
+    pub roll: String,
+    // The highest bet allowed with exploding dice.
+    pub exploding: Option<u16>,
+    pub power_availability: f64,
+    pub reroll_cost: i64,
+    pub flip_cost: i64,
+}
+
+impl Provenance {
+    // `at` is when the run started; pass `SystemTime::now()` outside of tests.
+    pub fn new(
+        mode: Mode,
+        seed: Option<u64>,
+        rules: &GameRules,
+        power_availability: f64,
+        costs: &PowerCosts,
+        at: SystemTime,
+    ) -> Self {
+        let (sampling, trials) = match mode {
+            Mode::Exact => ("exact", None),
+            Mode::MonteCarlo(trials) => ("monte carlo", Some(trials)),
+            Mode::Antithetic(trials) => ("antithetic", Some(trials)),
+        };
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            timestamp: at
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            sampling,
+            trials,
+            seed,
+            consolation: rules.consolation,
+            payout: rules.payout.to_string(),
+            faces: rules.faces.to_string(),
+            roll: rules.roll.to_string(),
+            exploding: rules.exploding,
+            power_availability,
+            reroll_cost: costs.reroll,
+            flip_cost: costs.flip,
+        }
+    }
+
+    // Every field as a name and its value, for formats without nesting.
+    pub fn parameters(&self) -> Vec<(&'static str, String)> {
+        let none = || "none".to_owned();
+        vec![
+            ("version", self.version.to_owned()),
+            ("timestamp", self.timestamp.to_string()),
+            ("sampling", self.sampling.to_owned()),
+            ("trials", self.trials.map_or_else(none, |n| n.to_string())),
+            ("seed", self.seed.map_or_else(none, |seed| seed.to_string())),
+            ("consolation", self.consolation.to_string()),
+            ("payout", self.payout.clone()),
+            ("faces", self.faces.clone()),
+            ("roll", self.roll.clone()),
+            (
+                "exploding",
+                self.exploding
+                    .map_or_else(none, |max| format!("up to {}", max)),
+            ),
+            ("power availability", self.power_availability.to_string()),
+            ("reroll cost", self.reroll_cost.to_string()),
+            ("flip cost", self.flip_cost.to_string()),
+        ]
+    }
+}
+
+// The one-line summary above a results table, like `n = 1000000, seed = 7,
+// payout = bet, ...`.
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.sampling, self.trials) {
+            (_, None) => write!(f, "n = exact")?,
+            ("antithetic", Some(trials)) => write!(f, "n = {} antithetic", trials)?,
+            (_, Some(trials)) => write!(f, "n = {}", trials)?,
+        }
+        if let Some(seed) = self.seed {
+            write!(f, ", seed = {}", seed)?;
+        }
+        write!(
+            f,
+            ", payout = {}, consolation = {}, faces = {}, roll = {}, power availability = {}",
+            self.payout, self.consolation, self.faces, self.roll, self.power_availability,
+        )?;
+        if let Some(max) = self.exploding {
+            write!(f, ", exploding up to {}", max)?;
+        }
+        write!(f, ", v{}", self.version)
+    }
+}
+
+// Results along with how they were produced.
+#[derive(Debug, Serialize)]
+pub struct RunResult<T> {
+    pub provenance: Provenance,
+    pub results: T,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn header_with_a_fixed_seed_and_clock() {
+        let provenance = Provenance::new(
+            Mode::MonteCarlo(1000),
+            Some(7),
+            &GameRules::default(),
+            1.0,
+            &PowerCosts::default(),
+            UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        );
+        assert_eq!(
+            provenance.to_string(),
+            format!(
+                "n = 1000, seed = 7, payout = bet, consolation = 2, faces = 1,2,3,4,5,6, \
+                 roll = normal, power availability = 1, v{}",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+        assert_eq!(provenance.timestamp, 1_700_000_000);
+        assert!(provenance.parameters().contains(&("seed", "7".to_owned())));
+    }
+}
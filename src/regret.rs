@@ -0,0 +1,82 @@
+use std::fmt;
+
+use crate::{
+    check_probability, weighted_mean, ApplicablePower, Bet, Dice, GameRules, Outcome, Power,
+    PowerCosts, SimulationError,
+};
+
+// The most expected gold each bet can make: whenever the power is available,
+// use whichever one pays best on the roll, net of its cost.
+pub fn optimal_outcome(
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+) -> Result<Outcome, SimulationError> {
+    check_probability(power_availability)?;
+    rules.check_enumerable()?;
+    let powers = [
+        Power::None,
+        Power::Reroll(Dice::unchecked(1, 1)),
+        Power::RerollKeepBest(Dice::unchecked(1, 1)),
+        Power::FLIP,
+    ];
+    Ok(rules
+        .bets()
+        .map(|bet| {
+            let fresh = Dice::average_gold(bet, rules);
+            let average = weighted_mean(rules.roll.counts(rules.faces), |dice| {
+                let best = powers
+                    .iter()
+                    .map(|power| {
+                        power.expected_gold_given(dice, bet, rules, fresh)
+                            - f64::from(power.cost(costs))
+                    })
+                    .fold(f64::NEG_INFINITY, f64::max);
+                power_availability * best
+                    + (1.0 - power_availability) * f64::from(dice.gold(bet, rules))
+            });
+            (bet, average)
+        })
+        .collect())
+}
+
+// How much expected gold each strategy gives up on each bet, against
+// `optimal`.
+pub fn regret_analysis<'a>(
+    strategies: &[(&'a str, &Outcome)],
+    optimal: &Outcome,
+) -> Vec<(&'a str, Vec<f64>)> {
+    strategies
+        .iter()
+        .map(|&(name, outcome)| (name, outcome.regret(optimal)))
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct RegretTable<'a> {
+    pub bets: Vec<Bet>,
+    pub regrets: Vec<(&'a str, Vec<f64>)>,
+}
+
+impl fmt::Display for RegretTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Strategy")?;
+        for bet in &self.bets {
+            write!(f, " | {}", bet)?;
+        }
+        writeln!(f, " | Total")?;
+        write!(f, "--------")?;
+        for bet in &self.bets {
+            write!(f, " | {}", "-".repeat(bet.to_string().len()))?;
+        }
+        writeln!(f, " | -----")?;
+        for (name, regret) in &self.regrets {
+            write!(f, " {:<44}", name)?;
+            for r in regret {
+                write!(f, " | {:>5.2}", r)?;
+            }
+            writeln!(f, " | {:>6.2}", regret.iter().sum::<f64>())?;
+        }
+        Ok(())
+    }
+}
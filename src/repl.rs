@@ -0,0 +1,80 @@
+use std::io::{BufRead, Write};
+
+use rand::RngCore;
+
+use crate::{find_strategy, Bet, GameRules, Mode, PowerCosts, SimulationError, Strategy};
+
+const HELP: &str = "\
+Commands:
+  roll                    roll the dice
+  round STRATEGY BET      play one round of STRATEGY at BET
+  ev STRATEGY BET         STRATEGY's exact expected gold at BET
+  strategies              list the strategies
+  help                    show this message";
+
+// Reads commands from `input` until it closes, answering each on `output`.
+// Strategies are named as printed or squashed together, like `RerollIfLosing`.
+// A bad command is reported and skipped rather than ending the session.
+#[allow(clippy::too_many_arguments)]
+pub fn repl<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    strategies: &[Box<dyn Strategy>],
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+    rng: &mut dyn RngCore,
+) -> Result<(), SimulationError> {
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let answer = match words.as_slice() {
+            [] => continue,
+            ["help"] => Ok(HELP.to_owned()),
+            ["strategies"] => Ok(strategies
+                .iter()
+                .map(|strategy| strategy.name())
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ["roll"] => {
+                let dice = rules.roll.roll(rules.faces, rng);
+                Ok(format!("{} = {}", dice, dice.sum()))
+            }
+            [command @ ("round" | "ev"), name @ .., bet] if !name.is_empty() => {
+                match (find_strategy(strategies, &name.join(" ")), bet.parse()) {
+                    (Ok(strategy), Ok(bet)) => Bet::new(bet, rules)
+                        .and_then(|bet| {
+                            if *command == "round" {
+                                Ok(strategy
+                                    .round(bet, rules, power_availability, costs, rng)
+                                    .to_string())
+                            } else {
+                                let outcome = strategy.outcome_mode(
+                                    Mode::Exact,
+                                    rules,
+                                    power_availability,
+                                    costs,
+                                    rng,
+                                )?;
+                                Ok(format!("{:.4}", outcome[bet.value()]))
+                            }
+                        })
+                        .map_err(|err| err.to_string()),
+                    (Err(err), _) => Err(err.to_string()),
+                    (_, Err(_)) => Err(format!("{:?} isn't a bet", bet)),
+                }
+            }
+            _ => Err(format!("unknown command {:?}, try help", line.trim())),
+        };
+        match answer {
+            Ok(answer) => writeln!(output, "{}", answer)?,
+            Err(err) => writeln!(output, "error: {}", err)?,
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::Path;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ApplicablePower, Bet, Dice, GameRules, Power, PowerCosts, Round, SimulationError, Strategy,
+};
+
+// Where a replayed round's dice come from, opening rolls and rerolls alike.
+pub trait RollSource {
+    // None once there are no rolls left.
+    fn next_roll(&mut self) -> Option<Dice>;
+}
+
+// Rolls as the rules say, keeping every roll it hands out so the session can
+// be played again exactly.
+pub struct RecordingRollSource<R> {
+    rng: R,
+    rules: GameRules,
+    rolls: Vec<Dice>,
+}
+
+impl<R: Rng> RecordingRollSource<R> {
+    pub fn new(rng: R, rules: GameRules) -> Self {
+        Self {
+            rng,
+            rules,
+            rolls: Vec::new(),
+        }
+    }
+
+    pub fn recorded(&self) -> RecordedDice {
+        RecordedDice {
+            rolls: self.rolls.clone(),
+            next: 0,
+        }
+    }
+}
+
+impl<R: Rng> RollSource for RecordingRollSource<R> {
+    fn next_roll(&mut self) -> Option<Dice> {
+        let dice = self
+            .rules
+            .roll
+            .roll(self.rules.faces, &mut self.rng)
+            .explode(&self.rules, &mut self.rng);
+        self.rolls.push(dice);
+        Some(dice)
+    }
+}
+
+// A saved sequence of rolls, handed out in order.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RecordedDice {
+    pub rolls: Vec<Dice>,
+    #[serde(skip)]
+    next: usize,
+}
+
+impl RecordedDice {
+    pub fn from_json(json: &str) -> Result<Self, SimulationError> {
+        serde_json::from_str(json).map_err(|err| SimulationError::InvalidRecording(err.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SimulationError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SimulationError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| SimulationError::InvalidRecording(err.to_string()))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+impl RollSource for RecordedDice {
+    fn next_roll(&mut self) -> Option<Dice> {
+        let dice = self.rolls.get(self.next).copied()?;
+        self.next += 1;
+        Some(dice)
+    }
+}
+
+// A round with its opening roll and any reroll taken from `source`, or None
+// if it runs out partway. The power is always available and the budget is
+// fresh, so the same rolls always play out the same way.
+pub fn replay_round<S: Strategy + ?Sized>(
+    strategy: &S,
+    bet: Bet,
+    rules: &GameRules,
+    costs: &PowerCosts,
+    source: &mut dyn RollSource,
+) -> Option<Round> {
+    let rolled = source.next_roll()?;
+    let power = match strategy.choose_power_within(bet, rolled, &strategy.budget()) {
+        Power::Reroll(_) => Power::Reroll(source.next_roll()?),
+        Power::RerollKeepBest(_) => Power::RerollKeepBest(source.next_roll()?),
+        power => power,
+    };
+    let dice = power.apply(rolled);
+    Some(Round {
+        bet,
+        rolled,
+        power,
+        dice,
+        won: bet <= dice.sum(),
+        gold: dice.gold(bet, rules),
+        cost: power.cost(costs),
+    })
+}
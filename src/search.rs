@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::SimulationBuilder;
+use crate::{
+    Bet, Dice, Die, Faces, GameRules, Mode, Outcome, Power, PowerCosts, SimulationError, Strategy,
+};
+
+// A complete policy: the power to use for every bet and every roll, keyed by
+// `(bet, d1, d2)` with `d1 <= d2`. Rolls missing from the table do nothing.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(into = "PolicyRecord", try_from = "PolicyRecord")]
+pub struct PolicyTable {
+    pub faces: Faces,
+    pub powers: BTreeMap<(u16, u8, u8), Power>,
+}
+
+impl PolicyTable {
+    // Does nothing on every roll of every bet.
+    pub fn empty(rules: &GameRules) -> Self {
+        let mut powers = BTreeMap::new();
+        for bet in rules.bets() {
+            for dice in rolls(rules.faces) {
+                powers.insert((bet.value(), dice.d1.face(), dice.d2.face()), Power::None);
+            }
+        }
+        Self {
+            faces: rules.faces,
+            powers,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SimulationError> {
+        serde_json::from_str(json).map_err(|err| SimulationError::InvalidPolicy(err.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SimulationError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SimulationError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| SimulationError::InvalidPolicy(err.to_string()))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+impl Strategy for PolicyTable {
+    fn name(&self) -> &str {
+        "Policy table"
+    }
+
+    fn id(&self) -> &str {
+        "PolicyTable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Uses the power for each bet and roll in the table loaded with --policy."
+    }
+
+    fn choose_power(&self, bet: Bet, dice: Dice) -> Power {
+        self.powers
+            .get(&(bet.value(), dice.d1.face(), dice.d2.face()))
+            .copied()
+            .unwrap_or(Power::None)
+    }
+}
+
+// Every distinct roll of two dice with these faces, lower die first.
+pub(crate) fn rolls(faces: Faces) -> impl Iterator<Item = Dice> {
+    let values = faces.values().to_vec();
+    (0..values.len()).flat_map(move |i| {
+        let values = values.clone();
+        (i..values.len())
+            .map(move |j| Dice::sorted(Die::unchecked(values[i]), Die::unchecked(values[j]), faces))
+    })
+}
+
+// How tables are saved: for each bet, each roll like `"3,5"` with its power
+// like `"reroll"`, so a table can be read and edited by hand.
+#[derive(Serialize, Deserialize)]
+struct PolicyRecord {
+    faces: String,
+    bets: BTreeMap<u16, BTreeMap<String, String>>,
+}
+
+impl From<PolicyTable> for PolicyRecord {
+    fn from(table: PolicyTable) -> Self {
+        let mut bets = BTreeMap::<u16, BTreeMap<String, String>>::new();
+        for ((bet, d1, d2), power) in table.powers {
+            bets.entry(bet)
+                .or_default()
+                .insert(format!("{},{}", d1, d2), power.to_string());
+        }
+        Self {
+            faces: table.faces.to_string(),
+            bets,
+        }
+    }
+}
+
+impl TryFrom<PolicyRecord> for PolicyTable {
+    type Error = SimulationError;
+
+    fn try_from(record: PolicyRecord) -> Result<Self, Self::Error> {
+        let faces: Faces = record.faces.parse()?;
+        let mut powers = BTreeMap::new();
+        for (bet, choices) in record.bets {
+            for (roll, power) in choices {
+                let dice = match roll.split_once(',') {
+                    Some((d1, d2)) => match (d1.trim().parse(), d2.trim().parse()) {
+                        (Ok(d1), Ok(d2)) => Dice::try_new(d1, d2, faces)?,
+                        _ => return Err(SimulationError::InvalidPolicy(roll)),
+                    },
+                    None => return Err(SimulationError::InvalidPolicy(roll)),
+                };
+                let power = power.parse::<Power>()?.check(faces)?;
+                powers.insert((bet, dice.d1.face(), dice.d2.face()), power);
+            }
+        }
+        Ok(Self { faces, powers })
+    }
+}
+
+// Improves a table one roll at a time: for each bet and roll in turn, switch
+// to whichever power earns the most at that bet, then go round again until
+// nothing changes. A roll's choice only affects its own bet's expected gold,
+// so with exact evaluation this ends at an optimal policy. Ties keep the
+// earlier of doing nothing, rerolling, rerolling and keeping the better roll,
+// and flipping, as `MarkovOptimal` does. Sampled evaluation reuses `seed` for
+// every candidate.
+#[allow(clippy::too_many_arguments)]
+pub fn hill_climb(
+    mode: Mode,
+    seed: u64,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+) -> Result<(PolicyTable, Outcome), SimulationError> {
+    let powers = [
+        Power::None,
+        Power::Reroll(Dice::unchecked(1, 1)),
+        Power::RerollKeepBest(Dice::unchecked(1, 1)),
+        Power::FLIP,
+    ];
+    let evaluate = |table: &PolicyTable| {
+        SimulationBuilder::default()
+            .mode(mode)
+            .seed(seed)
+            .rules(*rules)
+            .power_availability(power_availability)
+            .costs(*costs)
+            .run(table)
+    };
+    let mut table = PolicyTable::empty(rules);
+    let mut outcome = evaluate(&table)?;
+    let cells: Vec<(u16, u8, u8)> = table.powers.keys().copied().collect();
+    loop {
+        let mut changed = false;
+        for &cell in &cells {
+            let bet = cell.0;
+            for &power in &powers {
+                let current = table.powers[&cell];
+                if power == current {
+                    continue;
+                }
+                table.powers.insert(cell, power);
+                let candidate = evaluate(&table)?;
+                if candidate[bet] > outcome[bet] + 1e-9 {
+                    outcome = candidate;
+                    changed = true;
+                } else {
+                    table.powers.insert(cell, current);
+                }
+            }
+        }
+        if !changed {
+            return Ok((table, outcome));
+        }
+    }
+}
+
+// Each bet's table of choices, rows by the lower die and columns by the
+// higher, with its expected gold: `-` does nothing, `R` rerolls, `K` rerolls
+// keeping the better roll, and `F` flips.
+pub struct PolicyGrid<'a> {
+    pub table: &'a PolicyTable,
+    pub outcome: &'a Outcome,
+}
+
+impl fmt::Display for PolicyGrid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = self.table.faces.values();
+        for (bet, ev) in self.outcome.iter() {
+            writeln!(f, "Bet {}: {:.4}", bet, ev)?;
+            write!(f, "   ")?;
+            for value in values {
+                write!(f, " {:>2}", value)?;
+            }
+            writeln!(f)?;
+            for &d1 in values {
+                write!(f, " {:>2}", d1)?;
+                for &d2 in values {
+                    let symbol = match self.table.powers.get(&(bet.value(), d1, d2)) {
+                        None => " ",
+                        Some(Power::None) => "-",
+                        Some(Power::Reroll(_)) => "R",
+                        Some(Power::RerollKeepBest(_)) => "K",
+                        Some(Power::FlipOne(_)) => "F",
+                    };
+                    write!(f, " {:>2}", symbol)?;
+                }
+                writeln!(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt;
+
+use rand::RngCore;
+
+use crate::sizing::{BetTable, FixedBet, SizingStrategy};
+use crate::{check_probability, Bet, GameRules, Gold, PowerCosts, SimulationError, Strategy};
+
+// A session is `rounds` consecutive plays of the game starting from `bankroll`
+// gold, with each round's bet chosen by a `SizingStrategy`.
+//
+// In stake mode the bet is wagered from the bankroll: a win returns the stake
+// along with the round's gold, a loss forfeits it. The bet is capped at the
+// remaining bankroll, and the player is ruined (ending the session early) once
+// the bankroll can't cover the smallest bet. Power costs come out of the
+// bankroll either way, and the strategy's power budget has to last the whole
+// session.
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub bankroll: Gold,
+    pub rounds: u32,
+    pub stake: bool,
+    pub rules: GameRules,
+    pub power_availability: f64,
+    pub costs: PowerCosts,
+}
+
+impl Session {
+    pub fn check(&self) -> Result<(), SimulationError> {
+        if self.rounds == 0 {
+            return Err(SimulationError::InvalidRoundCount);
+        }
+        // Growth is measured against the starting bankroll.
+        if self.bankroll <= Gold::ZERO {
+            return Err(SimulationError::InvalidBankroll(self.bankroll.0));
+        }
+        check_probability(self.power_availability)?;
+        Ok(())
+    }
+
+    pub fn play<S: Strategy + ?Sized>(
+        &self,
+        strategy: &S,
+        bet: Bet,
+        rng: &mut dyn RngCore,
+    ) -> Result<SessionResult, SimulationError> {
+        self.play_sized(strategy, &FixedBet(bet), rng)
+    }
+
+    pub fn play_sized<S: Strategy + ?Sized, Z: SizingStrategy + ?Sized>(
+        &self,
+        strategy: &S,
+        sizing: &Z,
+        rng: &mut dyn RngCore,
+    ) -> Result<SessionResult, SimulationError> {
+        let mut bankroll = self.bankroll;
+        let mut budget = strategy.budget();
+        let min_bet = self.rules.min_bet();
+        let table = BetTable::new(self);
+        for _ in 0..self.rounds {
+            if self.stake {
+                if bankroll < min_bet.value().into() {
+                    return Ok(SessionResult {
+                        bankroll,
+                        ruined: true,
+                    });
+                }
+                let chosen = sizing.choose_bet(bankroll, &table);
+                let bet = Bet::unchecked(bankroll.0.min(chosen.value().into()) as u16);
+                let round = strategy.round_within(
+                    bet,
+                    &self.rules,
+                    self.power_availability,
+                    &self.costs,
+                    &mut budget,
+                    rng,
+                );
+                bankroll = if round.won {
+                    bankroll.checked_add(round.gold)?
+                } else {
+                    bankroll.checked_sub(bet.value().into())?
+                };
+                bankroll = bankroll.checked_sub(round.cost)?;
+            } else {
+                let bet = sizing.choose_bet(bankroll, &table);
+                bankroll = bankroll.checked_add(
+                    strategy
+                        .round_within(
+                            bet,
+                            &self.rules,
+                            self.power_availability,
+                            &self.costs,
+                            &mut budget,
+                            rng,
+                        )
+                        .net(),
+                )?;
+            }
+        }
+        Ok(SessionResult {
+            bankroll,
+            ruined: self.stake && bankroll < min_bet.value().into(),
+        })
+    }
+
+    pub fn summarize<S: Strategy + ?Sized>(
+        &self,
+        strategy: &S,
+        bet: Bet,
+        sessions: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<BankrollSummary, SimulationError> {
+        self.summarize_sized(strategy, &FixedBet(bet), sessions, rng)
+    }
+
+    pub fn summarize_sized<S: Strategy + ?Sized, Z: SizingStrategy + ?Sized>(
+        &self,
+        strategy: &S,
+        sizing: &Z,
+        sessions: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<BankrollSummary, SimulationError> {
+        if sessions == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        self.check()?;
+        let results: Vec<SessionResult> = (0..sessions)
+            .map(|_| self.play_sized(strategy, sizing, rng))
+            .collect::<Result<_, _>>()?;
+        let ruined = results.iter().filter(|result| result.ruined).count();
+        let mut finals: Vec<Gold> = results.iter().map(|result| result.bankroll).collect();
+        finals.sort_unstable();
+        let median = percentile(&finals, 50.0);
+        Ok(BankrollSummary {
+            mean: finals.iter().map(|&b| f64::from(b)).sum::<f64>() / finals.len() as f64,
+            median,
+            p5: percentile(&finals, 5.0),
+            p95: percentile(&finals, 95.0),
+            growth: (f64::from(median.max(Gold::ZERO)) / f64::from(self.bankroll))
+                .powf(1.0 / self.rounds as f64)
+                - 1.0,
+            ruin: ruined as f64 / finals.len() as f64,
+        })
+    }
+
+    pub fn outcome<S: Strategy + ?Sized>(
+        &self,
+        strategy: &S,
+        sessions: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<SessionOutcome, SimulationError> {
+        Ok(SessionOutcome(
+            self.rules
+                .bets()
+                .map(|bet| Ok((bet, self.summarize(strategy, bet, sessions, rng)?)))
+                .collect::<Result<_, SimulationError>>()?,
+        ))
+    }
+
+    pub fn compare_sizing<S: Strategy + ?Sized>(
+        &self,
+        strategy: &S,
+        strategies: &[&dyn SizingStrategy],
+        sessions: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<SizingOutcome, SimulationError> {
+        Ok(SizingOutcome(
+            strategies
+                .iter()
+                .map(|sizing| {
+                    Ok((
+                        sizing.name(),
+                        self.summarize_sized(strategy, *sizing, sessions, rng)?,
+                    ))
+                })
+                .collect::<Result<_, SimulationError>>()?,
+        ))
+    }
+}
+
+// Nearest-rank percentile of an already-sorted, nonempty slice.
+fn percentile(sorted: &[Gold], p: f64) -> Gold {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.max(1) - 1]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SessionResult {
+    pub bankroll: Gold,
+    pub ruined: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct BankrollSummary {
+    pub mean: f64,
+    pub median: Gold,
+    pub p5: Gold,
+    pub p95: Gold,
+    // Per-round growth of the median bankroll.
+    pub growth: f64,
+    pub ruin: f64,
+}
+
+#[derive(Debug)]
+pub struct SessionOutcome(Vec<(Bet, BankrollSummary)>);
+
+impl fmt::Display for SessionOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Bet | Mean | Median | 5% | 95% | Growth | Ruin")?;
+        writeln!(f, "--- | ---- | ------ | -- | --- | ------ | ----")?;
+        for (bet, summary) in &self.0 {
+            writeln!(
+                f,
+                " {:>2} | {:>10.2} | {:>8} | {:>8} | {:>8} | {:>6.2}% | {:>6.2}%",
+                bet,
+                summary.mean,
+                summary.median,
+                summary.p5,
+                summary.p95,
+                summary.growth * 100.0,
+                summary.ruin * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct SizingOutcome(Vec<(String, BankrollSummary)>);
+
+impl fmt::Display for SizingOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Sizing | Mean | Median | 5% | 95% | Growth | Ruin")?;
+        writeln!(f, "------ | ---- | ------ | -- | --- | ------ | ----")?;
+        for (name, summary) in &self.0 {
+            writeln!(
+                f,
+                " {:<22} | {:>10.2} | {:>8} | {:>8} | {:>8} | {:>6.2}% | {:>6.2}%",
+                name,
+                summary.mean,
+                summary.median,
+                summary.p5,
+                summary.p95,
+                summary.growth * 100.0,
+                summary.ruin * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::mock::StepRng;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{AlwaysReroll, NoPower};
+
+    fn staked(bankroll: i64, rounds: u32) -> Session {
+        Session {
+            bankroll: Gold(bankroll),
+            rounds,
+            stake: true,
+            rules: GameRules::default(),
+            power_availability: 1.0,
+            costs: PowerCosts::default(),
+        }
+    }
+
+    #[test]
+    fn staked_costs_can_leave_the_bankroll_negative() {
+        let session = Session {
+            costs: PowerCosts {
+                reroll: 100,
+                flip: 0,
+            },
+            ..staked(12, 1)
+        };
+        let bet = Bet::new(7, &session.rules).unwrap();
+        let mut rng = SmallRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let result = session.play(&AlwaysReroll(), bet, &mut rng).unwrap();
+            assert!(result.bankroll < Gold::ZERO);
+            assert!(result.ruined);
+        }
+    }
+
+    #[test]
+    fn sessions_need_a_positive_bankroll() {
+        for bankroll in [0, -5] {
+            let session = staked(bankroll, 10);
+            let bet = Bet::new(7, &session.rules).unwrap();
+            assert!(matches!(
+                session.summarize(&NoPower(), bet, 5, &mut StepRng::new(0, 0)),
+                Err(SimulationError::InvalidBankroll(b)) if b == bankroll
+            ));
+        }
+    }
+
+    #[test]
+    fn percentiles_take_the_nearest_rank() {
+        let sorted: Vec<Gold> = (1..=10).map(Gold).collect();
+        assert_eq!(percentile(&sorted, 0.0), Gold(1));
+        assert_eq!(percentile(&sorted, 5.0), Gold(1));
+        assert_eq!(percentile(&sorted, 50.0), Gold(5));
+        assert_eq!(percentile(&sorted, 51.0), Gold(6));
+        assert_eq!(percentile(&sorted, 95.0), Gold(10));
+        assert_eq!(percentile(&sorted, 100.0), Gold(10));
+        let one = [Gold(-4)];
+        for p in [0.0, 5.0, 50.0, 95.0, 100.0] {
+            assert_eq!(percentile(&one, p), Gold(-4));
+        }
+    }
+
+    // An rng stuck at zero rolls snake eyes every time.
+    #[test]
+    fn losing_every_round_is_ruin() {
+        let session = staked(20, 10);
+        let bet = Bet::new(7, &session.rules).unwrap();
+        let result = session
+            .play(&NoPower(), bet, &mut StepRng::new(0, 0))
+            .unwrap();
+        assert_eq!(result.bankroll, Gold::ZERO);
+        assert!(result.ruined);
+        let summary = session
+            .summarize(&NoPower(), bet, 5, &mut StepRng::new(0, 0))
+            .unwrap();
+        assert_eq!(summary.ruin, 1.0);
+    }
+
+    #[test]
+    fn winning_every_round_is_not_ruin() {
+        let session = staked(20, 10);
+        let bet = Bet::new(2, &session.rules).unwrap();
+        let result = session
+            .play(&NoPower(), bet, &mut StepRng::new(0, 0))
+            .unwrap();
+        assert_eq!(result.bankroll, Gold(40));
+        assert!(!result.ruined);
+    }
+}
@@ -0,0 +1,376 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+
+use crate::stats::RunningStats;
+use crate::{
+    antithetic, check_probability, Bet, Faces, GameRules, Mode, Outcome, PowerCosts,
+    SimulationError, Strategy,
+};
+
+// Seeded from `seed` so runs repeat, or else from entropy.
+pub fn rng_from(seed: Option<u64>) -> SmallRng {
+    match seed {
+        Some(seed) => SmallRng::seed_from_u64(seed),
+        None => SmallRng::from_entropy(),
+    }
+}
+
+// Everything a run needs in one place, e.g.
+//
+//     SimulationBuilder::default().trials(10_000).seed(7).run(&RerollIfLosing())
+//
+// The default runs a million Monte Carlo trials of the standard game.
+#[derive(Clone, Copy, Debug)]
+pub struct SimulationBuilder {
+    mode: Mode,
+    seed: Option<u64>,
+    rules: GameRules,
+    power_availability: f64,
+    costs: PowerCosts,
+    // The first and last bet to run, if not every bet the dice can make.
+    bet_range: Option<(u16, u16)>,
+}
+
+impl Default for SimulationBuilder {
+    fn default() -> Self {
+        Self {
+            mode: Mode::MonteCarlo(1_000_000),
+            seed: None,
+            rules: GameRules::default(),
+            power_availability: 1.0,
+            costs: PowerCosts::default(),
+            bet_range: None,
+        }
+    }
+}
+
+impl SimulationBuilder {
+    // Sets the trial count of a sampling mode; exact runs ignore it.
+    pub fn trials(mut self, trials: u64) -> Self {
+        self.mode = match self.mode {
+            Mode::Exact => Mode::Exact,
+            Mode::MonteCarlo(_) => Mode::MonteCarlo(trials),
+            Mode::Antithetic(_) => Mode::Antithetic(trials),
+        };
+        self
+    }
+
+    // Makes sampled runs repeatable.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn rules(mut self, rules: GameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn dice_config(mut self, faces: Faces) -> Self {
+        self.rules.faces = faces;
+        self
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn power_availability(mut self, power_availability: f64) -> Self {
+        self.power_availability = power_availability;
+        self
+    }
+
+    pub fn costs(mut self, costs: PowerCosts) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    // Only runs these bets, which `run` checks against the dice.
+    pub fn bet_range(mut self, range: RangeInclusive<u16>) -> Self {
+        self.bet_range = Some((*range.start(), *range.end()));
+        self
+    }
+
+    // Every bet to run, in increasing order.
+    fn bets(&self) -> Result<Vec<Bet>, SimulationError> {
+        let bets = self.rules.bets();
+        let (min, max) = match self.bet_range {
+            Some(range) => range,
+            None => return Ok(bets.collect()),
+        };
+        let (min, max) = (Bet::new(min, &self.rules)?, Bet::new(max, &self.rules)?);
+        if min > max {
+            return Err(SimulationError::InvalidBet {
+                bet: min.value(),
+                min: *self.rules.bet_range().start(),
+                max: max.value(),
+            });
+        }
+        Ok(bets.filter(|&bet| min <= bet && bet <= max).collect())
+    }
+
+    pub fn run<S: Strategy + ?Sized>(&self, strategy: &S) -> Result<Outcome, SimulationError> {
+        let (rules, power_availability, costs) =
+            (&self.rules, self.power_availability, &self.costs);
+        let bets = self.bets()?;
+        if matches!(self.mode, Mode::Exact) && strategy.is_deterministic() {
+            return Ok(strategy
+                .exact_outcome(rules, power_availability, costs)?
+                .filter(|bet| bets.contains(&bet)));
+        }
+        let (trials, antithetic) = match self.mode {
+            Mode::Exact => (1_000_000, false),
+            Mode::MonteCarlo(trials) => (trials, false),
+            Mode::Antithetic(trials) => (trials, true),
+        };
+        if trials == 0 {
+            return Err(SimulationError::InvalidTrialCount);
+        }
+        check_probability(power_availability)?;
+        let mut rng = rng_from(self.seed);
+        if antithetic {
+            let pairs = trials.div_ceil(2);
+            return Ok(bets
+                .into_iter()
+                .map(|bet| {
+                    let average = (0..pairs)
+                        .map(|_| {
+                            antithetic::pair_outcome(
+                                strategy,
+                                bet,
+                                rules,
+                                power_availability,
+                                costs,
+                                &mut rng,
+                            )
+                        })
+                        .sum::<f64>()
+                        / pairs as f64;
+                    (bet, average)
+                })
+                .collect());
+        }
+        Ok(bets
+            .into_iter()
+            .map(|bet| {
+                let mut stats = RunningStats::default();
+                for _ in 0..trials {
+                    stats.push(
+                        strategy
+                            .outcome(bet, rules, power_availability, costs, &mut rng)
+                            .into(),
+                    );
+                }
+                (bet, stats)
+            })
+            .collect())
+    }
+}
+
+// A simulation that can be extended: run some trials, look at the results,
+// then run more on top of them.
+pub struct Simulator<S: Strategy> {
+    strategy: S,
+    rules: GameRules,
+    power_availability: f64,
+    costs: PowerCosts,
+    trials_run: u64,
+    bets: Vec<Bet>,
+    // One per bet, in the same order as `bets`.
+    accumulators: Vec<RunningStats>,
+}
+
+impl<S: Strategy> Simulator<S> {
+    pub fn new(
+        strategy: S,
+        rules: GameRules,
+        power_availability: f64,
+        costs: PowerCosts,
+    ) -> Result<Self, SimulationError> {
+        check_probability(power_availability)?;
+        let bets: Vec<Bet> = rules.bets().collect();
+        Ok(Self {
+            strategy,
+            rules,
+            power_availability,
+            costs,
+            trials_run: 0,
+            accumulators: vec![RunningStats::default(); bets.len()],
+            bets,
+        })
+    }
+
+    // Plays `additional` more rounds at each bet.
+    pub fn run_more(&mut self, additional: u64, rng: &mut dyn RngCore) {
+        for (&bet, accumulator) in self.bets.iter().zip(&mut self.accumulators) {
+            for _ in 0..additional {
+                accumulator.push(
+                    self.strategy
+                        .outcome(bet, &self.rules, self.power_availability, &self.costs, rng)
+                        .into(),
+                );
+            }
+        }
+        self.trials_run += additional;
+    }
+
+    // Adds the trials another simulation of the same bets has run, such as one
+    // run in parallel or in an earlier session.
+    pub fn merge<T: Strategy>(&mut self, other: &Simulator<T>) -> Result<(), SimulationError> {
+        if self.bets != other.bets {
+            return Err(SimulationError::MismatchedBets);
+        }
+        for (accumulator, theirs) in self.accumulators.iter_mut().zip(&other.accumulators) {
+            accumulator.merge(theirs);
+        }
+        self.trials_run += other.trials_run;
+        Ok(())
+    }
+
+    // Forgets every trial run so far, keeping the accumulators' storage, so a
+    // simulation can be rerun many times without reallocating.
+    pub fn reset(&mut self) {
+        self.accumulators.fill(RunningStats::default());
+        self.trials_run = 0;
+    }
+
+    pub fn trials_run(&self) -> u64 {
+        self.trials_run
+    }
+
+    pub fn accumulators(&self) -> impl Iterator<Item = (Bet, &RunningStats)> {
+        self.bets.iter().copied().zip(&self.accumulators)
+    }
+
+    // The average so far at each bet, which is NaN until a trial has run.
+    pub fn current_outcome(&self) -> Outcome {
+        self.accumulators()
+            .map(|(bet, accumulator)| (bet, *accumulator))
+            .collect()
+    }
+}
+
+// A sampled mean and how far to trust it.
+#[derive(Clone, Copy, Debug)]
+pub struct Estimate {
+    pub mean: f64,
+    pub std_error: f64,
+    pub trials: u64,
+}
+
+impl Estimate {
+    // Half the width of the 95% confidence interval.
+    pub fn half_width(&self) -> f64 {
+        1.96 * self.std_error
+    }
+}
+
+// Trials are run in batches of this many between checks on the precision.
+const BATCH: u64 = 1_000;
+
+// Samples `bet` until the 95% confidence interval is narrower than `epsilon`
+// either side of the mean, or `max_trials` have run. Low-variance bets stop
+// much sooner than a fixed trial count would.
+#[allow(clippy::too_many_arguments)]
+pub fn run_until_precise<S: Strategy + ?Sized>(
+    strategy: &S,
+    bet: Bet,
+    epsilon: f64,
+    max_trials: u64,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+    rng: &mut dyn RngCore,
+) -> Result<Estimate, SimulationError> {
+    if max_trials < 2 {
+        return Err(SimulationError::InvalidTrialCount);
+    }
+    if epsilon.is_nan() || epsilon <= 0.0 {
+        return Err(SimulationError::InvalidPrecision(epsilon));
+    }
+    check_probability(power_availability)?;
+    let mut accumulator = RunningStats::default();
+    loop {
+        let batch = BATCH.min(max_trials - accumulator.count());
+        for _ in 0..batch {
+            accumulator.push(
+                strategy
+                    .outcome(bet, rules, power_availability, costs, rng)
+                    .into(),
+            );
+        }
+        let estimate = Estimate {
+            mean: accumulator.mean(),
+            std_error: accumulator.std_dev() / (accumulator.count() as f64).sqrt(),
+            trials: accumulator.count(),
+        };
+        if estimate.half_width() < epsilon || estimate.trials >= max_trials {
+            return Ok(estimate);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EstimateOutcome(pub Vec<(Bet, Estimate)>);
+
+impl fmt::Display for EstimateOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Bet | Exp | 95% CI | Trials")?;
+        writeln!(f, "--- | --- | ------ | ------")?;
+        for (bet, estimate) in &self.0 {
+            writeln!(
+                f,
+                " {:>2} | {:>8.3} | ±{:<6.3} | {:>10}",
+                bet,
+                estimate.mean,
+                estimate.half_width(),
+                estimate.trials
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoPower;
+
+    fn estimate(bet: u16, epsilon: f64, max_trials: u64) -> Estimate {
+        let rules = GameRules::default();
+        run_until_precise(
+            &NoPower(),
+            Bet::new(bet, &rules).unwrap(),
+            epsilon,
+            max_trials,
+            &rules,
+            1.0,
+            &PowerCosts::default(),
+            &mut rng_from(Some(2)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn precise_bets_stop_early() {
+        // Every roll meets a bet of 2, so the first batch settles it.
+        let certain = estimate(2, 0.01, 1_000_000);
+        assert_eq!(certain.trials, BATCH);
+        assert_eq!((certain.mean, certain.std_error), (2.0, 0.0));
+        // A bet of 3 misses only on snake eyes.
+        let nearly = estimate(3, 0.05, 1_000_000);
+        assert!(nearly.trials < 10 * BATCH, "{} trials", nearly.trials);
+        assert!(nearly.half_width() < 0.05);
+    }
+
+    #[test]
+    fn imprecise_bets_stop_at_the_limit() {
+        let coin_flip = estimate(7, 0.001, 5_500);
+        assert_eq!(coin_flip.trials, 5_500);
+        assert!(coin_flip.half_width() >= 0.001);
+    }
+}
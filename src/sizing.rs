@@ -0,0 +1,188 @@
+use crate::session::Session;
+use crate::{weighted_mean, Bet, Dice, Gold};
+
+// Decides how much to bet each round of a session given the current bankroll.
+pub trait SizingStrategy {
+    fn name(&self) -> String;
+
+    fn choose_bet(&self, bankroll: Gold, table: &BetTable) -> Bet;
+}
+
+// Expected change in bankroll from a single unpowered round.
+pub fn expected_gain(bet: Bet, session: &Session) -> f64 {
+    weighted_mean(session.rules.roll.counts(session.rules.faces), |dice| {
+        if session.stake && dice.sum() < bet {
+            -(bet.value() as f64)
+        } else {
+            f64::from(dice.gold(bet, &session.rules))
+        }
+    })
+}
+
+// A staked bet is a wager won with probability `p` at odds of `b` (the average
+// winning gold over the bet), for which Kelly wagers `p - (1 - p) / b` of the
+// bankroll.
+fn kelly(bet: Bet, session: &Session) -> f64 {
+    let p = Dice::probability_of_winning(bet, &session.rules);
+    let wins = session
+        .rules
+        .roll
+        .counts(session.rules.faces)
+        .into_iter()
+        .filter(|(dice, _)| bet <= dice.sum());
+    let odds =
+        weighted_mean(wins, |dice| dice.gold(bet, &session.rules).into()) / bet.value() as f64;
+    p - (1.0 - p) / odds
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BetOdds {
+    pub bet: Bet,
+    pub gain: f64,
+    // The fraction of the bankroll Kelly would wager on a staked bet.
+    pub kelly: f64,
+}
+
+// Everything the sizing strategies weigh about each bet. None of it changes
+// from round to round, so a session works it out once up front.
+#[derive(Clone, Debug)]
+pub struct BetTable {
+    pub stake: bool,
+    pub min_bet: Bet,
+    // In increasing order of bet.
+    pub bets: Vec<BetOdds>,
+}
+
+impl BetTable {
+    pub fn new(session: &Session) -> Self {
+        Self {
+            stake: session.stake,
+            min_bet: session.rules.min_bet(),
+            bets: session
+                .rules
+                .bets()
+                .map(|bet| BetOdds {
+                    bet,
+                    gain: expected_gain(bet, session),
+                    kelly: kelly(bet, session),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn best_ev(&self) -> Bet {
+        best(self.bets.iter()).unwrap_or(self.min_bet)
+    }
+}
+
+fn best<'a>(bets: impl Iterator<Item = &'a BetOdds>) -> Option<Bet> {
+    bets.max_by(|a, b| a.gain.partial_cmp(&b.gain).unwrap())
+        .map(|odds| odds.bet)
+}
+
+pub struct FixedBet(pub Bet);
+
+impl SizingStrategy for FixedBet {
+    fn name(&self) -> String {
+        format!("Always bet {}", self.0)
+    }
+
+    fn choose_bet(&self, _bankroll: Gold, _table: &BetTable) -> Bet {
+        self.0
+    }
+}
+
+pub struct BestEv;
+
+impl SizingStrategy for BestEv {
+    fn name(&self) -> String {
+        "Best EV".to_owned()
+    }
+
+    fn choose_bet(&self, _bankroll: Gold, table: &BetTable) -> Bet {
+        table.best_ev()
+    }
+}
+
+// Bets the EV-optimal number, but falls back to the smallest bet once the
+// bankroll drops below `threshold`.
+pub struct Conservative {
+    pub threshold: Gold,
+}
+
+impl SizingStrategy for Conservative {
+    fn name(&self) -> String {
+        format!("Conservative below {}", self.threshold)
+    }
+
+    fn choose_bet(&self, bankroll: Gold, table: &BetTable) -> Bet {
+        if bankroll < self.threshold {
+            table.min_bet
+        } else {
+            table.best_ev()
+        }
+    }
+}
+
+// Picks the best-EV bet whose wager fits within `fraction` of its Kelly stake,
+// or the smallest bet if none does. Without stakes there's nothing to lose, so
+// this is just `BestEv`.
+pub struct FractionalKelly {
+    pub fraction: f64,
+}
+
+impl SizingStrategy for FractionalKelly {
+    fn name(&self) -> String {
+        format!("{}x Kelly", self.fraction)
+    }
+
+    fn choose_bet(&self, bankroll: Gold, table: &BetTable) -> Bet {
+        if !table.stake {
+            return table.best_ev();
+        }
+        best(table.bets.iter().filter(|odds| {
+            odds.bet.value() as f64 <= self.fraction * odds.kelly * f64::from(bankroll)
+        }))
+        .unwrap_or(table.min_bet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameRules, PowerCosts};
+
+    fn session(stake: bool) -> Session {
+        Session {
+            bankroll: Gold(100),
+            rounds: 10,
+            stake,
+            rules: GameRules::default(),
+            power_availability: 1.0,
+            costs: PowerCosts::default(),
+        }
+    }
+
+    #[test]
+    fn the_table_matches_the_per_bet_gains() {
+        let session = session(true);
+        let table = BetTable::new(&session);
+        assert_eq!(table.bets.len(), 11);
+        for odds in &table.bets {
+            assert_eq!(odds.gain, expected_gain(odds.bet, &session));
+        }
+        assert_eq!(BetTable::new(&self::session(false)).best_ev(), 7u16);
+    }
+
+    #[test]
+    fn kelly_falls_back_to_the_smallest_bet() {
+        let table = BetTable::new(&session(true));
+        let kelly = FractionalKelly { fraction: 0.5 };
+        assert_eq!(kelly.choose_bet(Gold(1), &table), table.min_bet);
+        let bet = kelly.choose_bet(Gold(1_000), &table);
+        assert!(table
+            .bets
+            .iter()
+            .any(|odds| odds.bet == bet && odds.kelly > 0.0));
+    }
+}
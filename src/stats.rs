@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+// The value a standard normal variable falls below with probability `p`, by
+// Acklam's rational approximation, which is good to about 1e-9.
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < 0.02425 {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - 0.02425 {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+// Running mean, variance, and range, updated one sample at a time with
+// Welford's algorithm so long runs neither overflow nor lose precision, in
+// constant memory.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct RunningStats {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl RunningStats {
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        if self.count == 1 {
+            self.min = x;
+            self.max = x;
+        } else {
+            self.min = self.min.min(x);
+            self.max = self.max.max(x);
+        }
+    }
+
+    // Folds in another run's samples, as if they'd been pushed here, using
+    // Chan et al.'s pairwise update.
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let weight = self.count as f64 * other.count as f64 / count as f64;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 += other.m2 + delta * delta * weight;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count = count;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    // NaN before the first sample.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.mean
+        }
+    }
+
+    // The sample variance; NaN before the second sample.
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            f64::NAN
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    // NaN before the first sample, like `mean`.
+    pub fn min(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.min
+        }
+    }
+
+    pub fn max(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.max
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: [f64; 9] = [2.0, 7.0, 7.0, 2.0, 12.0, -3.0, 7.0, 4.5, 2.0];
+
+    fn pushed(samples: &[f64]) -> RunningStats {
+        let mut stats = RunningStats::default();
+        samples.iter().for_each(|&x| stats.push(x));
+        stats
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn matches_the_naive_formulas() {
+        let stats = pushed(&SAMPLES);
+        let n = SAMPLES.len() as f64;
+        let mean = SAMPLES.iter().sum::<f64>() / n;
+        let variance = SAMPLES.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        assert_eq!(stats.count(), 9);
+        assert!(close(stats.mean(), mean));
+        assert!(close(stats.variance(), variance));
+        assert!(close(stats.std_dev(), variance.sqrt()));
+        assert_eq!((stats.min(), stats.max()), (-3.0, 12.0));
+    }
+
+    #[test]
+    fn too_few_samples_are_nan() {
+        let empty = RunningStats::default();
+        assert!(empty.mean().is_nan() && empty.variance().is_nan());
+        assert!(empty.min().is_nan() && empty.max().is_nan());
+        let one = pushed(&[4.0]);
+        assert_eq!((one.mean(), one.min(), one.max()), (4.0, 4.0, 4.0));
+        assert!(one.variance().is_nan());
+    }
+
+    #[test]
+    fn merging_is_pushing_in_sequence() {
+        let all = pushed(&SAMPLES);
+        for split in 0..=SAMPLES.len() {
+            let mut merged = pushed(&SAMPLES[..split]);
+            merged.merge(&pushed(&SAMPLES[split..]));
+            assert_eq!(merged.count(), all.count());
+            assert!(close(merged.mean(), all.mean()), "split at {}", split);
+            assert!(
+                close(merged.variance(), all.variance()),
+                "split at {}",
+                split
+            );
+            assert_eq!((merged.min(), merged.max()), (all.min(), all.max()));
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::compare::Comparison;
+use crate::{Bet, Outcome, SimulationError};
+
+// A strategy's showing at its best bet.
+#[derive(Clone, Debug)]
+pub struct Contender {
+    pub name: String,
+    pub bet: Bet,
+    pub ev: f64,
+}
+
+impl Contender {
+    pub fn best(name: &str, outcome: &Outcome) -> Self {
+        let (bet, ev) = outcome
+            .iter()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .expect("an outcome covers at least one bet");
+        Self {
+            name: name.to_owned(),
+            bet,
+            ev,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Standing {
+    // Contenders that no one ranked above them beats by more than noise share
+    // a rank.
+    pub rank: usize,
+    pub name: String,
+    pub bet: Bet,
+    pub ev: f64,
+    // How far behind the leader, and whether that's more than noise.
+    pub gap: f64,
+    pub significant: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Leaderboard(pub Vec<Standing>);
+
+// Ranks `contenders` by expected gold, where `compare(a, b)` measures how much
+// `a` beats `b` by. A contender's rank is one more than the number of
+// contenders above it that beat it significantly.
+pub fn rank(
+    mut contenders: Vec<Contender>,
+    mut compare: impl FnMut(&Contender, &Contender) -> Result<Comparison, SimulationError>,
+) -> Result<Leaderboard, SimulationError> {
+    contenders.sort_by(|a, b| b.ev.partial_cmp(&a.ev).unwrap());
+    let mut standings = Vec::with_capacity(contenders.len());
+    for (i, contender) in contenders.iter().enumerate() {
+        let beaten_by: Vec<bool> = contenders[..i]
+            .iter()
+            .map(|above| Ok(compare(above, contender)?.is_significant()))
+            .collect::<Result<_, SimulationError>>()?;
+        standings.push(Standing {
+            rank: 1 + beaten_by.iter().filter(|&&beaten| beaten).count(),
+            name: contender.name.clone(),
+            bet: contender.bet,
+            ev: contender.ev,
+            gap: contenders[0].ev - contender.ev,
+            significant: beaten_by.first().copied().unwrap_or(false),
+        });
+    }
+    Ok(Leaderboard(standings))
+}
+
+impl Leaderboard {
+    fn is_tied(&self, rank: usize) -> bool {
+        self.0
+            .iter()
+            .filter(|standing| standing.rank == rank)
+            .count()
+            > 1
+    }
+}
+
+// Shared ranks are marked with `=`, and gaps to the leader beyond noise with
+// `*`.
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Rank | Strategy | Bet | Exp | Gap")?;
+        writeln!(f, "---- | -------- | --- | --- | ---")?;
+        for standing in &self.0 {
+            writeln!(
+                f,
+                " {:>3} | {:<44} | {:>2} | {:>8.2} | {:>8.2}{}",
+                format!(
+                    "{}{}",
+                    if self.is_tied(standing.rank) { "=" } else { "" },
+                    standing.rank
+                ),
+                standing.name,
+                standing.bet,
+                standing.ev,
+                standing.gap,
+                if standing.significant { "*" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The strategy with the most expected gold at one bet, and how far ahead of
+// the runner-up it is. With only one strategy there's no runner-up, and the
+// margin is NaN.
+#[derive(Clone, Debug, Serialize)]
+pub struct Winner {
+    pub bet: Bet,
+    pub name: String,
+    pub ev: f64,
+    pub margin: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Winners(pub Vec<Winner>);
+
+// The winner at each bet the first outcome covers. Strategies missing a bet
+// don't compete at it, and ties go to whichever comes first.
+pub fn winners_by_bet(outcomes: &[(String, Outcome)]) -> Winners {
+    let bets = outcomes
+        .first()
+        .map_or_else(Vec::new, |(_, outcome)| outcome.bets.clone());
+    Winners(
+        bets.into_iter()
+            .map(|bet| {
+                let scores: Vec<(&str, f64)> = outcomes
+                    .iter()
+                    .filter_map(|(name, outcome)| Some((name.as_str(), outcome.get(bet.value())?)))
+                    .collect();
+                let best = (0..scores.len()).fold(0, |best, i| {
+                    if scores[i].1 > scores[best].1 {
+                        i
+                    } else {
+                        best
+                    }
+                });
+                let runner_up = scores
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != best)
+                    .map(|(_, &(_, ev))| ev)
+                    .fold(f64::NAN, f64::max);
+                let (name, ev) = scores[best];
+                Winner {
+                    bet,
+                    name: name.to_owned(),
+                    ev,
+                    margin: ev - runner_up,
+                }
+            })
+            .collect(),
+    )
+}
+
+impl fmt::Display for Winners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Bet | Strategy | Exp | Margin")?;
+        writeln!(f, "--- | -------- | --- | ------")?;
+        for winner in &self.0 {
+            writeln!(
+                f,
+                " {:>2} | {:<44} | {:>8.2} | {:>6.2}",
+                winner.bet, winner.name, winner.ev, winner.margin
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+
+use crate::stats::RunningStats;
+use crate::{check_probability, Bet, GameRules, PowerCosts, SimulationError, Strategy};
+
+// How the first player fared against the second over many rounds at one
+// pairing of bets, going by each round's net gold.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VersusRecord {
+    pub bets: (u16, u16),
+    pub wins: u64,
+    pub draws: u64,
+    pub losses: u64,
+    // The first player's net gold less the second's.
+    pub difference: RunningStats,
+}
+
+impl VersusRecord {
+    pub fn rounds(&self) -> u64 {
+        self.wins + self.draws + self.losses
+    }
+
+    fn percent(&self, count: u64) -> f64 {
+        100.0 * count as f64 / self.rounds() as f64
+    }
+}
+
+// Plays `a` at the first bet against `b` at the second for `trials` rounds.
+// Normally each player rolls their own dice; in a mirror match both get the
+// same dice, including any reroll they both take, so only their choices
+// differ.
+#[allow(clippy::too_many_arguments)]
+pub fn versus<A: Strategy + ?Sized, B: Strategy + ?Sized, R: Rng + ?Sized>(
+    (a, b): (&A, &B),
+    (bet_a, bet_b): (Bet, Bet),
+    trials: u64,
+    mirror: bool,
+    rng: &mut R,
+    rules: &GameRules,
+    power_availability: f64,
+    costs: &PowerCosts,
+) -> Result<VersusRecord, SimulationError> {
+    if trials == 0 {
+        return Err(SimulationError::InvalidTrialCount);
+    }
+    check_probability(power_availability)?;
+    let mut record = VersusRecord {
+        bets: (bet_a.value(), bet_b.value()),
+        ..VersusRecord::default()
+    };
+    for _ in 0..trials {
+        let seed = rng.gen();
+        let mut rng_a = SmallRng::seed_from_u64(seed);
+        let mut rng_b = SmallRng::seed_from_u64(if mirror { seed } else { rng.gen() });
+        let gold_a = a.outcome(bet_a, rules, power_availability, costs, &mut rng_a);
+        let gold_b = b.outcome(bet_b, rules, power_availability, costs, &mut rng_b);
+        match gold_a.cmp(&gold_b) {
+            Ordering::Greater => record.wins += 1,
+            Ordering::Equal => record.draws += 1,
+            Ordering::Less => record.losses += 1,
+        }
+        record.difference.push((gold_a - gold_b).into());
+    }
+    Ok(record)
+}
+
+// One row per pairing of bets, from the first player's side.
+pub struct VersusTable<'a>(pub &'a [VersusRecord]);
+
+impl fmt::Display for VersusTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Bets    | Win     | Draw    | Loss    | Gold difference")?;
+        writeln!(f, "----    | ---     | ----    | ----    | ---------------")?;
+        for record in self.0 {
+            writeln!(
+                f,
+                "{:>2} v {:>2} | {:>6.2}% | {:>6.2}% | {:>6.2}% | {:>+.4}",
+                record.bets.0,
+                record.bets.1,
+                record.percent(record.wins),
+                record.percent(record.draws),
+                record.percent(record.losses),
+                record.difference.mean()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoPower, RerollIfLosing};
+
+    fn play<A: Strategy, B: Strategy>(a: &A, b: &B, mirror: bool) -> VersusRecord {
+        let rules = GameRules::default();
+        let bet = Bet::new(7, &rules).unwrap();
+        versus(
+            (a, b),
+            (bet, bet),
+            2_000,
+            mirror,
+            &mut SmallRng::seed_from_u64(12),
+            &rules,
+            1.0,
+            &PowerCosts::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn a_mirror_match_against_yourself_is_all_draws() {
+        let record = play(&RerollIfLosing(), &RerollIfLosing(), true);
+        assert_eq!(record.draws, 2_000);
+        assert_eq!(record.difference.mean(), 0.0);
+    }
+
+    #[test]
+    fn swapping_players_swaps_wins_and_losses() {
+        let forward = play(&RerollIfLosing(), &NoPower(), true);
+        let backward = play(&NoPower(), &RerollIfLosing(), true);
+        assert_eq!(
+            (forward.wins, forward.draws),
+            (backward.losses, backward.draws)
+        );
+        assert_eq!(forward.losses, backward.wins);
+        assert!((forward.difference.mean() + backward.difference.mean()).abs() < 1e-9);
+        // With the same dice, rerolling only a losing roll never does worse.
+        assert_eq!(forward.losses, 0);
+        assert!(forward.wins > 0);
+    }
+}
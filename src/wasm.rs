@@ -0,0 +1,22 @@
+use wasm_bindgen::prelude::*;
+
+use crate::simulator::SimulationBuilder;
+use crate::{find_strategy, strategies, GameRules, PowerCosts};
+
+// Runs a strategy, picked by name as `find_strategy` matches it, over the standard game and
+// returns its `Outcome` as JSON. Always seeded: there's no entropy source to
+// fall back on in the browser, and nothing here may spawn threads.
+#[wasm_bindgen]
+pub fn simulate(strategy: &str, trials: u64, seed: u64) -> Result<String, JsValue> {
+    let rules = GameRules::default();
+    let strategies = strategies(&rules, &PowerCosts::default());
+    let strategy =
+        find_strategy(&strategies, strategy).map_err(|err| JsValue::from_str(&err.to_string()))?;
+    let outcome = SimulationBuilder::default()
+        .rules(rules)
+        .trials(trials)
+        .seed(seed)
+        .run(strategy)
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+    serde_json::to_string(&outcome).map_err(|err| JsValue::from_str(&err.to_string()))
+}
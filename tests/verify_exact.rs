@@ -0,0 +1,61 @@
+// Every strategy's sampled expected gold should land within three standard
+// errors of the exact value; if not, either the simulation or the exact sums
+// have a bug.
+
+use betting_problem::simulator::rng_from;
+use betting_problem::*;
+
+// A million rounds of every strategy at every bet takes over ten minutes
+// unoptimized, so debug builds sample fewer; `cargo test --release` runs the
+// full count.
+const TRIALS: u64 = if cfg!(debug_assertions) {
+    20_000
+} else {
+    1_000_000
+};
+
+fn verify(rules: GameRules, power_availability: f64, costs: PowerCosts) {
+    for strategy in strategies(&rules, &costs) {
+        let exact = strategy
+            .exact_outcome(&rules, power_availability, &costs)
+            .unwrap();
+        let simulated = strategy
+            .avg_outcome(
+                TRIALS,
+                &rules,
+                power_availability,
+                &costs,
+                &mut rng_from(Some(17)),
+            )
+            .unwrap();
+        assert_eq!(exact.bets, simulated.bets);
+        for (i, &bet) in exact.bets.iter().enumerate() {
+            let std_err = simulated.std_dev[i] / (TRIALS as f64).sqrt();
+            let error = (exact.ev[i] - simulated.ev[i]).abs();
+            assert!(
+                error < 3.0 * std_err + 1e-9,
+                "{} at bet {}: exact {}, simulated {} (standard error {})",
+                strategy.name(),
+                bet,
+                exact.ev[i],
+                simulated.ev[i],
+                std_err
+            );
+        }
+    }
+}
+
+#[test]
+fn simulation_matches_exact_for_the_standard_game() {
+    verify(GameRules::default(), 1.0, PowerCosts::default());
+}
+
+#[test]
+fn simulation_matches_exact_with_costs_and_scarce_powers() {
+    let rules = GameRules {
+        payout: PayoutRule::Sum,
+        consolation: 1,
+        ..GameRules::default()
+    };
+    verify(rules, 0.5, PowerCosts { reroll: 2, flip: 1 });
+}